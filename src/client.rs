@@ -0,0 +1,446 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, InstructionData};
+
+use crate::pda::*;
+use crate::{ExtraVoltData, PrincipalProtectionVaultV1, VoltVault};
+
+/**
+ * Deserialized volt state needed to derive every account the user instructions take.
+ * All derived round accounts are for the volt's current round (volt_vault.round_number).
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct LoadedVolt {
+    pub key: Pubkey,
+    pub volt_vault: VoltVault,
+    pub extra_volt_data: ExtraVoltData,
+    pub program_id: Pubkey,
+}
+
+impl LoadedVolt {
+    pub fn new(key: Pubkey, volt_vault: VoltVault, extra_volt_data: ExtraVoltData) -> Self {
+        LoadedVolt {
+            key,
+            volt_vault,
+            extra_volt_data,
+            program_id: crate::id(),
+        }
+    }
+
+    pub fn with_program_id(mut self, program_id: Pubkey) -> Self {
+        self.program_id = program_id;
+        self
+    }
+
+    pub fn extra_volt_data_key(&self) -> Pubkey {
+        find_extra_volt_data_address(&self.key, Some(&self.program_id)).0
+    }
+
+    pub fn round_info_key(&self, round_number: u64) -> Pubkey {
+        find_round_info_address(&self.key, round_number, Some(&self.program_id)).0
+    }
+
+    pub fn round_volt_tokens_key(&self, round_number: u64) -> Pubkey {
+        find_round_volt_tokens_address(&self.key, round_number, Some(&self.program_id)).0
+    }
+
+    pub fn round_underlying_tokens_key(&self, round_number: u64) -> Pubkey {
+        find_round_underlying_tokens_address(&self.key, round_number, Some(&self.program_id)).0
+    }
+
+    pub fn round_ul_pending_key(&self, round_number: u64) -> Pubkey {
+        find_round_ul_pending_address(&self.key, round_number, Some(&self.program_id)).0
+    }
+
+    pub fn epoch_info_key(&self, round_number: u64) -> Pubkey {
+        find_epoch_info_address(&self.key, round_number, Some(&self.program_id)).0
+    }
+
+    pub fn pending_deposit_key(&self, user: &Pubkey) -> Pubkey {
+        find_pending_deposit_address(&self.key, user, Some(&self.program_id)).0
+    }
+
+    pub fn pending_withdrawal_key(&self, user: &Pubkey) -> Pubkey {
+        find_pending_withdrawal_address(&self.key, user, Some(&self.program_id)).0
+    }
+
+    /// volt-specific accounts to append to deposit_ix and deposit_with_claim_ix, derived from self.program_id
+    pub fn deposit_remaining_account_metas(
+        &self,
+        pp_vault: Option<&PrincipalProtectionVaultV1>,
+    ) -> Result<Vec<AccountMeta>> {
+        crate::remaining_accounts::deposit_remaining_account_metas(
+            &self.key,
+            &self.volt_vault,
+            &self.extra_volt_data,
+            pp_vault,
+            Some(&self.program_id),
+        )
+    }
+
+    fn current_round(&self) -> u64 {
+        self.volt_vault.round_number
+    }
+}
+
+/**
+ * User side of an instruction.
+ *  NOTES:
+ *  1. authority_check is the key the pending deposit/withdrawal PDAs are derived from
+ *  2. in the common (wallet) case payer_authority, non_payer_authority and authority_check are all the same key
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct VoltUser {
+    pub payer_authority: Pubkey,
+    pub non_payer_authority: Pubkey,
+    pub authority_check: Pubkey,
+
+    // token account w/ mint == vault mint
+    pub vault_tokens: Pubkey,
+
+    // token account w/ mint == underlying mint
+    pub underlying_tokens: Pubkey,
+}
+
+impl VoltUser {
+    pub fn new(authority: Pubkey, vault_tokens: Pubkey, underlying_tokens: Pubkey) -> Self {
+        VoltUser {
+            payer_authority: authority,
+            non_payer_authority: authority,
+            authority_check: authority,
+            vault_tokens,
+            underlying_tokens,
+        }
+    }
+
+    pub fn with_payer(mut self, payer_authority: Pubkey) -> Self {
+        self.payer_authority = payer_authority;
+        self
+    }
+}
+
+fn build_ix(
+    volt: &LoadedVolt,
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+) -> Instruction {
+    Instruction {
+        program_id: volt.program_id,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// NOTE: volt-specific accounts must be appended as remaining accounts before sending, see LoadedVolt::deposit_remaining_account_metas
+pub fn deposit_ix(volt: &LoadedVolt, user: &VoltUser, deposit_amount: u64) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::Deposit {
+            payer_authority: user.payer_authority,
+            non_payer_authority: user.non_payer_authority,
+            authority_check: user.authority_check,
+            vault_mint: volt.volt_vault.vault_mint,
+            volt_vault: volt.key,
+            vault_authority: volt.volt_vault.vault_authority,
+            extra_volt_data: volt.extra_volt_data_key(),
+            whitelist: volt.extra_volt_data.whitelist,
+            deposit_pool: volt.volt_vault.deposit_pool,
+            writer_token_pool: volt.volt_vault.writer_token_pool,
+            user_vault_tokens: user.vault_tokens,
+            user_ul_tokens: user.underlying_tokens,
+            round_info: volt.round_info_key(round_number),
+            round_volt_tokens: volt.round_volt_tokens_key(round_number),
+            round_underlying_tokens: volt.round_underlying_tokens_key(round_number),
+            pending_deposit_info: volt.pending_deposit_key(&user.authority_check),
+            epoch_info: volt.epoch_info_key(round_number),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::Deposit { deposit_amount },
+    )
+}
+
+pub fn withdraw_ix(
+    volt: &LoadedVolt,
+    user: &VoltUser,
+    fee_acct: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::Withdraw {
+            payer_authority: user.payer_authority,
+            non_payer_authority: user.non_payer_authority,
+            authority_check: user.authority_check,
+            vault_mint: volt.volt_vault.vault_mint,
+            volt_vault: volt.key,
+            extra_volt_data: volt.extra_volt_data_key(),
+            vault_authority: volt.volt_vault.vault_authority,
+            whitelist: volt.extra_volt_data.whitelist,
+            deposit_pool: volt.volt_vault.deposit_pool,
+            underlying_token_destination: user.underlying_tokens,
+            vault_token_source: user.vault_tokens,
+            round_info: volt.round_info_key(round_number),
+            round_underlying_tokens: volt.round_underlying_tokens_key(round_number),
+            pending_withdrawal_info: volt.pending_withdrawal_key(&user.authority_check),
+            epoch_info: volt.epoch_info_key(round_number),
+            fee_acct: *fee_acct,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::ID,
+        },
+        crate::instruction::Withdraw { amount },
+    )
+}
+
+/// pending_deposit_round_number is PendingDeposit.round_number (0 if the account does not exist yet)
+/// NOTE: volt-specific accounts must be appended as remaining accounts before sending
+pub fn deposit_with_claim_ix(
+    volt: &LoadedVolt,
+    user: &VoltUser,
+    sol_transfer_authority: &Pubkey,
+    pending_deposit_round_number: u64,
+    amount: u64,
+    do_transfer: bool,
+) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::DepositWithClaim {
+            payer_authority: user.payer_authority,
+            non_payer_authority: user.non_payer_authority,
+            sol_transfer_authority: *sol_transfer_authority,
+            authority_check: user.authority_check,
+            vault_mint: volt.volt_vault.vault_mint,
+            volt_vault: volt.key,
+            vault_authority: volt.volt_vault.vault_authority,
+            extra_volt_data: volt.extra_volt_data_key(),
+            deposit_pool: volt.volt_vault.deposit_pool,
+            writer_token_pool: volt.volt_vault.writer_token_pool,
+            vault_token_destination: user.vault_tokens,
+            underlying_token_source: user.underlying_tokens,
+            round_info: volt.round_info_key(round_number),
+            round_underlying_tokens: volt.round_underlying_tokens_key(round_number),
+            pending_deposit_info: volt.pending_deposit_key(&user.authority_check),
+            pending_deposit_round_info: volt.round_info_key(pending_deposit_round_number),
+            pending_deposit_round_volt_tokens: volt
+                .round_volt_tokens_key(pending_deposit_round_number),
+            pending_deposit_round_underlying_tokens: volt
+                .round_underlying_tokens_key(pending_deposit_round_number),
+            epoch_info: volt.epoch_info_key(round_number),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::DepositWithClaim {
+            amount,
+            do_transfer,
+        },
+    )
+}
+
+/// pending_withdrawal_round_number is PendingWithdrawal.round_number (0 if the account does not exist yet)
+pub fn withdraw_with_claim_ix(
+    volt: &LoadedVolt,
+    user: &VoltUser,
+    fee_acct: &Pubkey,
+    pending_withdrawal_round_number: u64,
+    amount: u64,
+) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::WithdrawWithClaim {
+            payer_authority: user.payer_authority,
+            non_payer_authority: user.non_payer_authority,
+            authority_check: user.authority_check,
+            vault_mint: volt.volt_vault.vault_mint,
+            volt_vault: volt.key,
+            vault_authority: volt.volt_vault.vault_authority,
+            extra_volt_data: volt.extra_volt_data_key(),
+            deposit_pool: volt.volt_vault.deposit_pool,
+            underlying_token_destination: user.underlying_tokens,
+            vault_token_source: user.vault_tokens,
+            round_info: volt.round_info_key(round_number),
+            round_underlying_tokens: volt.round_underlying_tokens_key(round_number),
+            pending_withdrawal_info: volt.pending_withdrawal_key(&user.authority_check),
+            pending_withdrawal_round_info: volt.round_info_key(pending_withdrawal_round_number),
+            pending_withdrawal_round_underlying_tokens_for_pws: volt
+                .round_ul_pending_key(pending_withdrawal_round_number),
+            epoch_info: volt.epoch_info_key(round_number),
+            fee_acct: *fee_acct,
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+            rent: sysvar::rent::ID,
+        },
+        crate::instruction::WithdrawWithClaim { amount },
+    )
+}
+
+pub fn claim_pending_deposit_ix(
+    volt: &LoadedVolt,
+    user: &VoltUser,
+    pending_deposit_round_number: u64,
+) -> Instruction {
+    build_ix(
+        volt,
+        crate::accounts::ClaimPendingDeposit {
+            authority: user.authority_check,
+            volt_vault: volt.key,
+            extra_volt_data: volt.extra_volt_data_key(),
+            vault_authority: volt.volt_vault.vault_authority,
+            user_vault_tokens: user.vault_tokens,
+            pending_deposit_round_info: volt.round_info_key(pending_deposit_round_number),
+            pending_deposit_round_volt_tokens: volt
+                .round_volt_tokens_key(pending_deposit_round_number),
+            pending_deposit_info: volt.pending_deposit_key(&user.authority_check),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::ClaimPendingDeposit {},
+    )
+}
+
+pub fn claim_pending_withdrawal_ix(
+    volt: &LoadedVolt,
+    user: &VoltUser,
+    pending_withdrawal_round_number: u64,
+) -> Instruction {
+    build_ix(
+        volt,
+        crate::accounts::ClaimPendingWithdrawal {
+            authority: user.authority_check,
+            volt_vault: volt.key,
+            extra_volt_data: volt.extra_volt_data_key(),
+            vault_authority: volt.volt_vault.vault_authority,
+            vault_mint: volt.volt_vault.vault_mint,
+            underlying_token_destination: user.underlying_tokens,
+            pending_withdrawal_round_info: volt.round_info_key(pending_withdrawal_round_number),
+            pending_withdrawal_info: volt.pending_withdrawal_key(&user.authority_check),
+            round_underlying_tokens_for_pending_withdrawals: volt
+                .round_ul_pending_key(pending_withdrawal_round_number),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::ClaimPendingWithdrawal {},
+    )
+}
+
+pub fn cancel_pending_deposit_ix(volt: &LoadedVolt, user: &VoltUser) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::CancelPendingDeposit {
+            authority: user.authority_check,
+            volt_vault: volt.key,
+            extra_volt_data: volt.extra_volt_data_key(),
+            vault_authority: volt.volt_vault.vault_authority,
+            underlying_token_destination: user.underlying_tokens,
+            round_info: volt.round_info_key(round_number),
+            round_underlying_tokens: volt.round_underlying_tokens_key(round_number),
+            pending_deposit_info: volt.pending_deposit_key(&user.authority_check),
+            epoch_info: volt.epoch_info_key(round_number),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::CancelPendingDeposit {},
+    )
+}
+
+pub fn cancel_pending_withdrawal_ix(volt: &LoadedVolt, user: &VoltUser) -> Instruction {
+    let round_number = volt.current_round();
+    build_ix(
+        volt,
+        crate::accounts::CancelPendingWithdrawal {
+            authority: user.authority_check,
+            vault_mint: volt.volt_vault.vault_mint,
+            volt_vault: volt.key,
+            extra_volt_data: volt.extra_volt_data_key(),
+            vault_authority: volt.volt_vault.vault_authority,
+            vault_token_destination: user.vault_tokens,
+            round_info: volt.round_info_key(round_number),
+            pending_withdrawal_info: volt.pending_withdrawal_key(&user.authority_check),
+            epoch_info: volt.epoch_info_key(round_number),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        },
+        crate::instruction::CancelPendingWithdrawal {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+
+    fn loaded_volt() -> LoadedVolt {
+        let volt_vault = VoltVault {
+            round_number: 3,
+            vault_mint: Pubkey::new_unique(),
+            vault_authority: Pubkey::new_unique(),
+            deposit_pool: Pubkey::new_unique(),
+            writer_token_pool: Pubkey::new_unique(),
+            ..VoltVault::default()
+        };
+        let extra_volt_data = ExtraVoltData {
+            whitelist: Pubkey::new_unique(),
+            ..ExtraVoltData::default()
+        };
+        LoadedVolt::new(Pubkey::new_unique(), volt_vault, extra_volt_data)
+    }
+
+    fn user() -> VoltUser {
+        VoltUser::new(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        )
+    }
+
+    #[test]
+    fn deposit_ix_accounts() {
+        let volt = loaded_volt();
+        let user = user();
+        let ix = deposit_ix(&volt, &user, 100);
+
+        assert_eq!(ix.program_id, crate::ID);
+        assert_eq!(ix.data[..8], hash(b"global:deposit").to_bytes()[..8]);
+        assert_eq!(ix.data[8..], 100u64.to_le_bytes());
+
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys[0], user.payer_authority);
+        assert!(ix.accounts[0].is_signer);
+        assert!(keys.contains(&find_round_info_address(&volt.key, 3, None).0));
+        assert!(
+            keys.contains(&find_pending_deposit_address(&volt.key, &user.authority_check, None).0)
+        );
+        assert!(keys.contains(&find_extra_volt_data_address(&volt.key, None).0));
+    }
+
+    #[test]
+    fn claim_uses_pending_round() {
+        let volt = loaded_volt();
+        let user = user();
+        let ix = claim_pending_deposit_ix(&volt, &user, 2);
+
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&find_round_info_address(&volt.key, 2, None).0));
+        assert!(!keys.contains(&find_round_info_address(&volt.key, 3, None).0));
+    }
+
+    #[test]
+    fn with_program_id_derives_from_program_id() {
+        let program_id = Pubkey::new_unique();
+        let volt = loaded_volt().with_program_id(program_id);
+        let user = user();
+        let ix = withdraw_ix(&volt, &user, &Pubkey::new_unique(), 5);
+
+        assert_eq!(ix.program_id, program_id);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert!(keys.contains(&find_extra_volt_data_address(&volt.key, Some(&program_id)).0));
+        assert!(!keys.contains(&find_extra_volt_data_address(&volt.key, None).0));
+        assert_eq!(*keys.last().unwrap(), sysvar::rent::ID);
+    }
+}
@@ -3,6 +3,11 @@ use std::collections::{BTreeMap, BTreeSet};
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 
+use crate::pda::{
+    EPOCH_INFO_SEED, EXTRA_VOLT_DATA_SEED, PENDING_DEPOSIT_SEED, PENDING_WITHDRAWAL_SEED,
+    PROTECTION_VAULT_SEED, ROUND_INFO_SEED, ROUND_UL_PENDING_SEED, ROUND_UNDERLYING_TOKENS_SEED,
+    ROUND_VOLT_TOKENS_SEED,
+};
 use crate::{
     ExtraVoltData, FriktionEpochInfo, PendingDeposit, PendingWithdrawal,
     PrincipalProtectionVaultV1, Round, VoltVault,
@@ -47,7 +52,7 @@ pub struct PrincipalProtectionContextAccounts<'info> {
     #[account(
         seeds = [
           &volt_vault.key().to_bytes()[..],
-          b"protectionVault"
+          PROTECTION_VAULT_SEED
       ],
       bump
     )]
@@ -112,7 +117,7 @@ pub struct Deposit<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -142,30 +147,30 @@ pub struct Deposit<'info> {
     pub user_ul_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundVoltTokens"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_VOLT_TOKENS_SEED],
         bump)]
     pub round_volt_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundUnderlyingTokens"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
         bump)]
     pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(init_if_needed,
         space=PendingDeposit::LEN + 8,
-        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref() ,b"pendingDeposit"],
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref() ,PENDING_DEPOSIT_SEED],
         bump,
         payer = payer_authority)]
     pub pending_deposit_info: Box<Account<'info, PendingDeposit>>,
 
     #[account(
         mut,
-        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump,
     )]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
@@ -198,7 +203,7 @@ pub struct Withdraw<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
     )]
@@ -224,25 +229,25 @@ pub struct Withdraw<'info> {
 
     // round accounts
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundUnderlyingTokens"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
         bump)]
     pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(init_if_needed,
         space=PendingWithdrawal::LEN + 8,
-        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref(), b"pendingWithdrawal"],
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref(), PENDING_WITHDRAWAL_SEED],
         bump,
         payer=payer_authority
     )]
     pub pending_withdrawal_info: Box<Account<'info, PendingWithdrawal>>,
 
     #[account(mut,
-        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump,
     )]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
@@ -290,7 +295,7 @@ pub struct DepositWithClaim<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -313,42 +318,42 @@ pub struct DepositWithClaim<'info> {
     pub underlying_token_source: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundUnderlyingTokens"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
         bump)]
     pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(init_if_needed,
         space=PendingDeposit::LEN + 8,
-        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref() ,b"pendingDeposit"],
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref() ,PENDING_DEPOSIT_SEED],
         bump,
         payer = payer_authority)]
     pub pending_deposit_info: Box<Account<'info, PendingDeposit>>,
 
     /// CHECK: skip, checked by PDA, can't require an object because may not exist (if round number is 0)
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , ROUND_INFO_SEED],
         bump)]
     pub pending_deposit_round_info: AccountInfo<'info>,
 
     /// CHECK: skip, checked by PDA, can't require an object because may not exist (if round number is 0)
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , b"roundVoltTokens"],
+        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , ROUND_VOLT_TOKENS_SEED],
         bump)]
     pub pending_deposit_round_volt_tokens: AccountInfo<'info>,
 
     /// CHECK: skip, checked by PDA, can't require an object because may not exist (if round number is 0)
     #[account(mut,
-            seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , b"roundUnderlyingTokens"],
+            seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , ROUND_UNDERLYING_TOKENS_SEED],
             bump)]
     pub pending_deposit_round_underlying_tokens: AccountInfo<'info>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump)]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
 
@@ -385,7 +390,7 @@ pub struct WithdrawWithClaim<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -404,36 +409,36 @@ pub struct WithdrawWithClaim<'info> {
 
     // round accounts
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundUnderlyingTokens"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
         bump)]
     pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(init_if_needed,
         space=PendingWithdrawal::LEN + 8,
-        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref(), b"pendingWithdrawal"],
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref(), PENDING_WITHDRAWAL_SEED],
         bump,
         payer = payer_authority)]
     pub pending_withdrawal_info: Box<Account<'info, PendingWithdrawal>>,
 
     /// CHECK: skip, checked by PDA. may be a non-existent account if there is no pendign withdrawal to process
     #[account(mut,
-      seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , b"roundInfo"],
+      seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , ROUND_INFO_SEED],
       bump)]
     pub pending_withdrawal_round_info: AccountInfo<'info>,
 
     /// CHECK: skip, checked by PDA. may be a non-existent account if there is no pendign withdrawal to process
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , b"roundUlPending"],
+        seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , ROUND_UL_PENDING_SEED],
     bump)]
     pub pending_withdrawal_round_underlying_tokens_for_pws: AccountInfo<'info>,
 
     #[account(mut,
-        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump,
     )]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
@@ -456,7 +461,7 @@ pub struct ClaimPendingDeposit<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -471,17 +476,17 @@ pub struct ClaimPendingDeposit<'info> {
     pub user_vault_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , ROUND_INFO_SEED],
         bump)]
     pub pending_deposit_round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , b"roundVoltTokens"],
+        seeds = [volt_vault.key().as_ref(), pending_deposit_info.round_number.to_le_bytes().as_ref() , ROUND_VOLT_TOKENS_SEED],
         bump)]
     pub pending_deposit_round_volt_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), b"pendingDeposit"],
+        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), PENDING_DEPOSIT_SEED],
         bump)]
     pub pending_deposit_info: Box<Account<'info, PendingDeposit>>,
 
@@ -498,7 +503,7 @@ pub struct ClaimPendingWithdrawal<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -516,18 +521,18 @@ pub struct ClaimPendingWithdrawal<'info> {
     pub underlying_token_destination: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), pending_withdrawal_info.round_number.to_le_bytes().as_ref() , ROUND_INFO_SEED],
         bump)]
     pub pending_withdrawal_round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), b"pendingWithdrawal"],
+        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), PENDING_WITHDRAWAL_SEED],
         bump,
     )]
     pub pending_withdrawal_info: Box<Account<'info, PendingWithdrawal>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), (pending_withdrawal_info.round_number).to_le_bytes().as_ref() ,b"roundUlPending"],
+        seeds = [volt_vault.key().as_ref(), (pending_withdrawal_info.round_number).to_le_bytes().as_ref() ,ROUND_UL_PENDING_SEED],
         bump,
     )]
     pub round_underlying_tokens_for_pending_withdrawals: Box<Account<'info, TokenAccount>>,
@@ -545,7 +550,7 @@ pub struct CancelPendingDeposit<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -561,22 +566,22 @@ pub struct CancelPendingDeposit<'info> {
 
     // round accounts
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-      seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundUnderlyingTokens"],
+      seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
       bump)]
     pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), b"pendingDeposit"],
+        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), PENDING_DEPOSIT_SEED],
         bump)]
     pub pending_deposit_info: Box<Account<'info, PendingDeposit>>,
 
     #[account(mut,
-        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump,
     )]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
@@ -597,7 +602,7 @@ pub struct CancelPendingWithdrawal<'info> {
     #[account(
         seeds = [
             &volt_vault.key().to_bytes()[..],
-            b"extraVoltData"
+            EXTRA_VOLT_DATA_SEED
         ],
         bump,
       )]
@@ -612,17 +617,17 @@ pub struct CancelPendingWithdrawal<'info> {
 
     // round accounts
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,b"roundInfo"],
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
         bump)]
     pub round_info: Box<Account<'info, Round>>,
 
     #[account(mut,
-        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), b"pendingWithdrawal"],
+        seeds = [volt_vault.key().as_ref(), authority.key().as_ref(), PENDING_WITHDRAWAL_SEED],
         bump)]
     pub pending_withdrawal_info: Box<Account<'info, PendingWithdrawal>>,
 
     #[account(mut,
-        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,b"epochInfo"],
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
         bump,
     )]
     pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
@@ -631,3 +636,418 @@ pub struct CancelPendingWithdrawal<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
 }
+
+///////// embeddable account groups ///////////
+
+/**
+ * Account groups to nest inside the Accounts struct of a program that CPIs into the volt program,
+ * so that it validates the volt accounts before spending compute on the CPI.
+ *  NOTES:
+ *  1. constraints mirror Deposit and Withdraw above. PDAs are derived from the volt program id (seeds::program), not the caller's
+ *  2. each group carries its own volt_vault, since nested constraints can't reference fields of the parent struct.
+ *     VoltDepositAccounts and VoltWithdrawAccounts tie the groups to a single volt. a caller nesting the groups directly
+ *     must do the same, e.g. #[account(constraint = round.volt_vault.key() == core.volt_vault.key())]
+ *  3. pending deposit/withdrawal accounts are AccountInfos, the volt program inits them if needed
+ *  4. user token accounts are checked against deposit_pool.mint, the mint the volt takes deposits in, which is not
+ *     always volt_vault.underlying_asset_mint
+ */
+#[derive(Accounts, Clone)]
+pub struct VoltVaultCoreAccounts<'info> {
+    #[account(mut)]
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(address=volt_vault.vault_authority)]
+    /// CHECK: skip, checked by macro
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address=volt_vault.vault_mint)]
+    pub vault_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            &volt_vault.key().to_bytes()[..],
+            EXTRA_VOLT_DATA_SEED
+        ],
+        bump,
+        seeds::program = crate::ID,
+    )]
+    pub extra_volt_data: Box<Account<'info, ExtraVoltData>>,
+
+    #[account(address=extra_volt_data.whitelist)]
+    /// CHECK: skip, checked by macro
+    pub whitelist: AccountInfo<'info>,
+
+    #[account(mut, address=volt_vault.deposit_pool)]
+    pub deposit_pool: Box<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts, Clone)]
+pub struct VoltRoundAccounts<'info> {
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    pub round_info: Box<Account<'info, Round>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_VOLT_TOKENS_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    pub round_volt_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
+}
+
+#[derive(Accounts, Clone)]
+pub struct VoltUserDepositAccounts<'info> {
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(address=volt_vault.deposit_pool @ crate::error::ErrorCode::DepositPoolDoesNotMatchVoltVault)]
+    pub deposit_pool: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: skip, pending deposit is derived from it
+    pub authority_check: AccountInfo<'info>,
+
+    #[account(mut,
+        constraint = user_vault_tokens.mint == volt_vault.vault_mint @ crate::error::ErrorCode::VaultMintDoesNotMatchUserTokenAccount)]
+    pub user_vault_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        constraint = user_ul_tokens.mint == deposit_pool.mint @ crate::error::ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount)]
+    pub user_ul_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref() ,PENDING_DEPOSIT_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    /// CHECK: skip, may not be initialized yet
+    pub pending_deposit_info: AccountInfo<'info>,
+}
+
+#[derive(Accounts, Clone)]
+pub struct VoltUserWithdrawAccounts<'info> {
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(address=volt_vault.deposit_pool @ crate::error::ErrorCode::DepositPoolDoesNotMatchVoltVault)]
+    pub deposit_pool: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: skip, pending withdrawal is derived from it
+    pub authority_check: AccountInfo<'info>,
+
+    #[account(mut,
+        constraint = underlying_token_destination.mint == deposit_pool.mint @ crate::error::ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount)]
+    pub underlying_token_destination: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        constraint = vault_token_source.mint == volt_vault.vault_mint @ crate::error::ErrorCode::VaultMintDoesNotMatchUserTokenAccount)]
+    pub vault_token_source: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), authority_check.key().as_ref(), PENDING_WITHDRAWAL_SEED],
+        bump,
+        seeds::program = crate::ID)]
+    /// CHECK: skip, may not be initialized yet
+    pub pending_withdrawal_info: AccountInfo<'info>,
+}
+
+// groups of a deposit into a single volt
+#[derive(Accounts, Clone)]
+pub struct VoltDepositAccounts<'info> {
+    pub core: VoltVaultCoreAccounts<'info>,
+
+    #[account(constraint = round.volt_vault.key() == core.volt_vault.key())]
+    pub round: VoltRoundAccounts<'info>,
+
+    #[account(constraint = user.volt_vault.key() == core.volt_vault.key())]
+    pub user: VoltUserDepositAccounts<'info>,
+}
+
+// groups of a withdrawal from a single volt
+#[derive(Accounts, Clone)]
+pub struct VoltWithdrawAccounts<'info> {
+    pub core: VoltVaultCoreAccounts<'info>,
+
+    #[account(constraint = round.volt_vault.key() == core.volt_vault.key())]
+    pub round: VoltRoundAccounts<'info>,
+
+    #[account(constraint = user.volt_vault.key() == core.volt_vault.key())]
+    pub user: VoltUserWithdrawAccounts<'info>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preflight::PreflightVolt;
+    use crate::test_utils::{account_info, mint_account, program_account, token_account};
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+
+    ///////// account groups ///////////
+
+    struct Volt {
+        key: Pubkey,
+        vault: VoltVault,
+        deposit_mint: Pubkey,
+        info: AccountInfo<'static>,
+    }
+
+    impl Volt {
+        fn new() -> Self {
+            let key = Pubkey::new_unique();
+            let vault = VoltVault {
+                vault_mint: Pubkey::new_unique(),
+                vault_authority: Pubkey::new_unique(),
+                deposit_pool: Pubkey::new_unique(),
+                underlying_asset_mint: Pubkey::new_unique(),
+                round_number: 1,
+                ..VoltVault::default()
+            };
+            Volt {
+                key,
+                vault,
+                deposit_mint: Pubkey::new_unique(),
+                info: program_account(key, crate::id(), &vault),
+            }
+        }
+
+        fn pda(&self, seeds: &[&[u8]]) -> Pubkey {
+            let mut seeds = seeds.to_vec();
+            seeds.insert(0, self.key.as_ref());
+            Pubkey::find_program_address(&seeds, &crate::id()).0
+        }
+
+        fn round_pda(&self, seed: &[u8]) -> Pubkey {
+            self.pda(&[&self.vault.round_number.to_le_bytes(), seed])
+        }
+
+        fn deposit_pool(&self) -> AccountInfo<'static> {
+            token_account(
+                self.vault.deposit_pool,
+                self.deposit_mint,
+                self.vault.vault_authority,
+                0,
+            )
+        }
+
+        fn core(&self) -> Vec<AccountInfo<'static>> {
+            let whitelist = Pubkey::new_unique();
+            let extra_volt_data = ExtraVoltData {
+                whitelist,
+                ..ExtraVoltData::default()
+            };
+            vec![
+                self.info.clone(),
+                account_info(
+                    self.vault.vault_authority,
+                    Pubkey::default(),
+                    vec![],
+                    false,
+                    false,
+                ),
+                mint_account(self.vault.vault_mint, self.vault.vault_authority, 0),
+                program_account(
+                    self.pda(&[EXTRA_VOLT_DATA_SEED]),
+                    crate::id(),
+                    &extra_volt_data,
+                ),
+                account_info(whitelist, Pubkey::default(), vec![], false, false),
+                self.deposit_pool(),
+            ]
+        }
+
+        fn round(&self) -> Vec<AccountInfo<'static>> {
+            let owner = self.vault.vault_authority;
+            vec![
+                self.info.clone(),
+                program_account(
+                    self.round_pda(ROUND_INFO_SEED),
+                    crate::id(),
+                    &Round::default(),
+                ),
+                token_account(
+                    self.round_pda(ROUND_VOLT_TOKENS_SEED),
+                    self.vault.vault_mint,
+                    owner,
+                    0,
+                ),
+                token_account(
+                    self.round_pda(ROUND_UNDERLYING_TOKENS_SEED),
+                    self.deposit_mint,
+                    owner,
+                    0,
+                ),
+                program_account(
+                    self.round_pda(EPOCH_INFO_SEED),
+                    crate::id(),
+                    &FriktionEpochInfo::default(),
+                ),
+            ]
+        }
+
+        // user_ul_tokens (or underlying_token_destination) has mint ul_mint
+        fn user(&self, ul_mint: Pubkey, pending_seed: &[u8]) -> Vec<AccountInfo<'static>> {
+            let authority = Pubkey::new_unique();
+            let vault_tokens =
+                token_account(Pubkey::new_unique(), self.vault.vault_mint, authority, 0);
+            let ul_tokens = token_account(Pubkey::new_unique(), ul_mint, authority, 0);
+            let (first, second) = if pending_seed == PENDING_DEPOSIT_SEED {
+                (vault_tokens, ul_tokens)
+            } else {
+                (ul_tokens, vault_tokens)
+            };
+            vec![
+                self.info.clone(),
+                self.deposit_pool(),
+                account_info(authority, Pubkey::default(), vec![], false, false),
+                first,
+                second,
+                account_info(
+                    self.pda(&[authority.as_ref(), pending_seed]),
+                    Pubkey::default(),
+                    vec![],
+                    false,
+                    true,
+                ),
+            ]
+        }
+    }
+
+    fn accounts_error<'info, T: Accounts<'info>>(accounts: &[AccountInfo<'info>]) -> Option<Error> {
+        T::try_accounts(
+            &crate::id(),
+            &mut &accounts[..],
+            &[],
+            &mut BTreeMap::new(),
+            &mut BTreeSet::new(),
+        )
+        .err()
+    }
+
+    #[test]
+    fn user_groups_check_mints_against_deposit_pool() {
+        let volt = Volt::new();
+        let underlying_asset_mint = volt.vault.underlying_asset_mint;
+
+        let deposit = volt.user(volt.deposit_mint, PENDING_DEPOSIT_SEED);
+        assert!(accounts_error::<VoltUserDepositAccounts>(&deposit).is_none());
+        let deposit = volt.user(underlying_asset_mint, PENDING_DEPOSIT_SEED);
+        assert_eq!(
+            accounts_error::<VoltUserDepositAccounts>(&deposit),
+            Some(error!(
+                crate::error::ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount
+            ))
+        );
+
+        let withdraw = volt.user(volt.deposit_mint, PENDING_WITHDRAWAL_SEED);
+        assert!(accounts_error::<VoltUserWithdrawAccounts>(&withdraw).is_none());
+        let withdraw = volt.user(underlying_asset_mint, PENDING_WITHDRAWAL_SEED);
+        assert_eq!(
+            accounts_error::<VoltUserWithdrawAccounts>(&withdraw),
+            Some(error!(
+                crate::error::ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount
+            ))
+        );
+
+        // a deposit pool of another volt with the same mint
+        let mut deposit = volt.user(volt.deposit_mint, PENDING_DEPOSIT_SEED);
+        deposit[1] = token_account(
+            Pubkey::new_unique(),
+            volt.deposit_mint,
+            volt.vault.vault_authority,
+            0,
+        );
+        assert_eq!(
+            accounts_error::<VoltUserDepositAccounts>(&deposit),
+            Some(error!(
+                crate::error::ErrorCode::DepositPoolDoesNotMatchVoltVault
+            ))
+        );
+    }
+
+    #[test]
+    fn wrong_mint_withdraw_destination_matches_preflight() {
+        let volt = Volt::new();
+        let withdraw = volt.user(volt.vault.underlying_asset_mint, PENDING_WITHDRAWAL_SEED);
+        let unpack =
+            |info: &AccountInfo| SplTokenAccount::unpack(&info.try_borrow_data().unwrap()).unwrap();
+        let deposit_pool = unpack(&withdraw[1]);
+        let underlying_token_destination = unpack(&withdraw[3]);
+        let user_vault_tokens = unpack(&withdraw[4]);
+
+        let volt_vault = VoltVault {
+            initialized: true,
+            round_has_started: true,
+            ..volt.vault
+        };
+        let preflight = PreflightVolt {
+            volt_vault: &volt_vault,
+            extra_volt_data: &ExtraVoltData::default(),
+            round: &Round::default(),
+            deposit_pool: &deposit_pool,
+            whitelist: None,
+        };
+        let preflight_error = preflight
+            .withdraw(
+                &Pubkey::default(),
+                None,
+                &user_vault_tokens,
+                &underlying_token_destination,
+                0,
+                volt_vault.start_transfer_time as i64,
+            )
+            .err()
+            .map(|error_code| error!(error_code));
+
+        assert!(preflight_error.is_some());
+        assert_eq!(
+            accounts_error::<VoltUserWithdrawAccounts>(&withdraw),
+            preflight_error
+        );
+    }
+
+    #[test]
+    fn composed_groups_share_one_volt() {
+        let volt = Volt::new();
+        let other = Volt::new();
+        let accounts = |core: &Volt, round: &Volt, user: &Volt, pending_seed: &[u8]| {
+            let mut accounts = core.core();
+            accounts.extend(round.round());
+            accounts.extend(user.user(user.deposit_mint, pending_seed));
+            accounts
+        };
+
+        let deposit = accounts(&volt, &volt, &volt, PENDING_DEPOSIT_SEED);
+        assert!(accounts_error::<VoltDepositAccounts>(&deposit).is_none());
+        let withdraw = accounts(&volt, &volt, &volt, PENDING_WITHDRAWAL_SEED);
+        assert!(accounts_error::<VoltWithdrawAccounts>(&withdraw).is_none());
+
+        let constraint_raw = Some(error!(anchor_lang::error::ErrorCode::ConstraintRaw));
+        let deposit = accounts(&volt, &other, &volt, PENDING_DEPOSIT_SEED);
+        assert_eq!(
+            accounts_error::<VoltDepositAccounts>(&deposit),
+            constraint_raw
+        );
+        let deposit = accounts(&volt, &volt, &other, PENDING_DEPOSIT_SEED);
+        assert_eq!(
+            accounts_error::<VoltDepositAccounts>(&deposit),
+            constraint_raw
+        );
+        let withdraw = accounts(&volt, &volt, &other, PENDING_WITHDRAWAL_SEED);
+        assert_eq!(
+            accounts_error::<VoltWithdrawAccounts>(&withdraw),
+            constraint_raw
+        );
+    }
+}
@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::ExtraVoltData;
+
+/**
+ * PDA of a DAO (or any other program) that holds a volt position on behalf of the program.
+ *  NOTES:
+ *  1. the PDA is both the non_payer_authority and the authority_check of user instructions, so pending deposit/withdrawal PDAs are derived from it
+ *  2. the PDA must be the owner of the token accounts the DAO deposits from and withdraws to
+ *  3. payer_authority only pays for (and receives the rent of) pending deposit/withdrawal accounts. it may be the PDA itself if the PDA is system owned and holds lamports
+ *  4. the volt must be a dao volt (extra_volt_data.is_for_dao) with extra_volt_data.dao_program_id == dao_program_id
+ */
+#[derive(Clone, Debug)]
+pub struct DaoAuthority<'a> {
+    pub dao_program_id: Pubkey,
+    pub key: Pubkey,
+
+    // seeds w/o the bump
+    pub seeds: Vec<&'a [u8]>,
+    pub bump: [u8; 1],
+}
+
+/**
+ * Authorities of user instructions (Deposit, Withdraw, DepositWithClaim, WithdrawWithClaim).
+ * Claim and cancel instructions take a single authority, which must be authority_check.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoltAuthorities {
+    pub payer_authority: Pubkey,
+    pub non_payer_authority: Pubkey,
+    pub authority_check: Pubkey,
+}
+
+impl<'a> DaoAuthority<'a> {
+    pub fn find(dao_program_id: &Pubkey, seeds: &[&'a [u8]]) -> Self {
+        let (key, bump) = Pubkey::find_program_address(seeds, dao_program_id);
+        DaoAuthority {
+            dao_program_id: *dao_program_id,
+            key,
+            seeds: seeds.to_vec(),
+            bump: [bump],
+        }
+    }
+
+    /// errors if seeds and bump do not derive a PDA of dao_program_id
+    pub fn with_bump(dao_program_id: &Pubkey, seeds: &[&'a [u8]], bump: u8) -> Result<Self> {
+        let bump_seed = [bump];
+        let mut seeds_with_bump = seeds.to_vec();
+        seeds_with_bump.push(&bump_seed);
+        let key = Pubkey::create_program_address(&seeds_with_bump, dao_program_id)
+            .map_err(|_| error!(ErrorCode::InvalidDaoAuthority))?;
+        Ok(DaoAuthority {
+            dao_program_id: *dao_program_id,
+            key,
+            seeds: seeds.to_vec(),
+            bump: [bump],
+        })
+    }
+
+    /// seeds (incl. bump) to pass to invoke_signed or CpiContext::new_with_signer
+    pub fn signer_seeds(&self) -> Vec<&[u8]> {
+        let mut signer_seeds = self.seeds.clone();
+        signer_seeds.push(&self.bump);
+        signer_seeds
+    }
+
+    /// payer may be self.key if the DAO PDA pays for the pending deposit/withdrawal accounts itself
+    pub fn volt_authorities(&self, payer: &Pubkey) -> VoltAuthorities {
+        VoltAuthorities {
+            payer_authority: *payer,
+            non_payer_authority: self.key,
+            authority_check: self.key,
+        }
+    }
+
+    /// errors with the error the volt program returns if this PDA can't act on the volt
+    pub fn check_volt(&self, extra_volt_data: &ExtraVoltData) -> Result<()> {
+        if !extra_volt_data.is_for_dao {
+            return err!(ErrorCode::VoltMustBeForDao);
+        }
+        require_keys_eq!(
+            extra_volt_data.dao_program_id,
+            self.dao_program_id,
+            ErrorCode::InvalidDaoProgramId
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "cpi")]
+    fn check_authority(&self, authority: &AccountInfo) -> Result<()> {
+        require_keys_eq!(authority.key(), self.key, ErrorCode::InvalidDaoAuthority);
+        Ok(())
+    }
+}
+
+///////// CPI wrappers, signing as the DAO PDA ///////////
+
+#[cfg(feature = "cpi")]
+mod cpi_wrappers {
+    use super::*;
+    use crate::invoke::{
+        CancelPendingDepositAccounts, CancelPendingWithdrawalAccounts, ClaimPendingDepositAccounts,
+        ClaimPendingWithdrawalAccounts, DepositAccounts, DepositWithClaimAccounts,
+        WithdrawAccounts, WithdrawWithClaimAccounts,
+    };
+
+    // each wrapper checks the DAO PDA is the authority and calls the crate::invoke helper with the PDA's signer seeds.
+    // remaining_accounts are passed through as in crate::invoke
+
+    pub fn dao_deposit<'info>(
+        dao: &DaoAuthority,
+        accounts: DepositAccounts<'_, 'info>,
+        deposit_amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.non_payer_authority)?;
+        dao.check_authority(&accounts.authority_check)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::deposit(
+            accounts,
+            deposit_amount,
+            &[&signer_seeds[..]],
+            remaining_accounts,
+        )
+    }
+
+    pub fn dao_deposit_with_claim<'info>(
+        dao: &DaoAuthority,
+        accounts: DepositWithClaimAccounts<'_, 'info>,
+        amount: u64,
+        do_transfer: bool,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.non_payer_authority)?;
+        dao.check_authority(&accounts.authority_check)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::deposit_with_claim(
+            accounts,
+            amount,
+            do_transfer,
+            &[&signer_seeds[..]],
+            remaining_accounts,
+        )
+    }
+
+    pub fn dao_withdraw<'info>(
+        dao: &DaoAuthority,
+        accounts: WithdrawAccounts<'_, 'info>,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.non_payer_authority)?;
+        dao.check_authority(&accounts.authority_check)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::withdraw(accounts, amount, &[&signer_seeds[..]], remaining_accounts)
+    }
+
+    pub fn dao_withdraw_with_claim<'info>(
+        dao: &DaoAuthority,
+        accounts: WithdrawWithClaimAccounts<'_, 'info>,
+        amount: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.non_payer_authority)?;
+        dao.check_authority(&accounts.authority_check)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::withdraw_with_claim(
+            accounts,
+            amount,
+            &[&signer_seeds[..]],
+            remaining_accounts,
+        )
+    }
+
+    pub fn dao_claim_pending_deposit<'info>(
+        dao: &DaoAuthority,
+        accounts: ClaimPendingDepositAccounts<'_, 'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.authority)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::claim_pending_deposit(accounts, &[&signer_seeds[..]], remaining_accounts)
+    }
+
+    pub fn dao_claim_pending_withdrawal<'info>(
+        dao: &DaoAuthority,
+        accounts: ClaimPendingWithdrawalAccounts<'_, 'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.authority)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::claim_pending_withdrawal(accounts, &[&signer_seeds[..]], remaining_accounts)
+    }
+
+    pub fn dao_cancel_pending_deposit<'info>(
+        dao: &DaoAuthority,
+        accounts: CancelPendingDepositAccounts<'_, 'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.authority)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::cancel_pending_deposit(accounts, &[&signer_seeds[..]], remaining_accounts)
+    }
+
+    pub fn dao_cancel_pending_withdrawal<'info>(
+        dao: &DaoAuthority,
+        accounts: CancelPendingWithdrawalAccounts<'_, 'info>,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        dao.check_authority(&accounts.authority)?;
+        let signer_seeds = dao.signer_seeds();
+        crate::invoke::cancel_pending_withdrawal(accounts, &[&signer_seeds[..]], remaining_accounts)
+    }
+}
+
+#[cfg(feature = "cpi")]
+pub use cpi_wrappers::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: &[&[u8]] = &[b"treasury", &[7u8; 4]];
+
+    #[test]
+    fn find_and_with_bump_agree() {
+        let dao_program_id = Pubkey::new_unique();
+        let dao = DaoAuthority::find(&dao_program_id, SEEDS);
+        assert_eq!(
+            dao.key,
+            Pubkey::find_program_address(SEEDS, &dao_program_id).0
+        );
+        assert_eq!(
+            Pubkey::create_program_address(&dao.signer_seeds(), &dao_program_id).unwrap(),
+            dao.key
+        );
+
+        let with_bump = DaoAuthority::with_bump(&dao_program_id, SEEDS, dao.bump[0]).unwrap();
+        assert_eq!(with_bump.key, dao.key);
+
+        // a bump that doesn't derive the PDA of another program id
+        assert!(
+            DaoAuthority::with_bump(&Pubkey::new_unique(), SEEDS, dao.bump[0])
+                .map_or(true, |other| other.key != dao.key)
+        );
+    }
+
+    #[test]
+    fn authorities() {
+        let dao = DaoAuthority::find(&Pubkey::new_unique(), SEEDS);
+        let payer = Pubkey::new_unique();
+        assert_eq!(
+            dao.volt_authorities(&payer),
+            VoltAuthorities {
+                payer_authority: payer,
+                non_payer_authority: dao.key,
+                authority_check: dao.key,
+            }
+        );
+        assert_eq!(dao.volt_authorities(&dao.key).payer_authority, dao.key);
+    }
+
+    #[test]
+    fn check_volt() {
+        let dao = DaoAuthority::find(&Pubkey::new_unique(), SEEDS);
+        let mut extra_volt_data = ExtraVoltData::default();
+        assert_eq!(
+            dao.check_volt(&extra_volt_data).unwrap_err(),
+            error!(ErrorCode::VoltMustBeForDao)
+        );
+
+        extra_volt_data.is_for_dao = true;
+        extra_volt_data.dao_program_id = Pubkey::new_unique();
+        assert_eq!(
+            dao.check_volt(&extra_volt_data).unwrap_err(),
+            error!(ErrorCode::InvalidDaoProgramId)
+        );
+
+        extra_volt_data.dao_program_id = dao.dao_program_id;
+        assert!(dao.check_volt(&extra_volt_data).is_ok());
+    }
+
+    #[cfg(feature = "cpi")]
+    #[test]
+    fn cpi_wrappers_sign_as_the_dao() {
+        use crate::invoke::ClaimPendingDepositAccounts;
+        use crate::test_utils::{
+            account_info, set_test_syscall_stubs, take_invoked, token_account,
+        };
+        use anchor_spl::token::TokenAccount;
+
+        set_test_syscall_stubs();
+        take_invoked();
+
+        let dao = DaoAuthority::find(&Pubkey::new_unique(), SEEDS);
+        let info = |key| account_info(key, Pubkey::default(), vec![], false, true);
+        let vault_info = token_account(Pubkey::new_unique(), Pubkey::new_unique(), dao.key, 0);
+        let mut user_vault_tokens = Account::<TokenAccount>::try_from(&vault_info).unwrap();
+        let remaining = [info(Pubkey::new_unique())];
+        let mut claim = |authority| {
+            dao_claim_pending_deposit(
+                &dao,
+                ClaimPendingDepositAccounts {
+                    volt_program: info(crate::ID),
+                    authority: info(authority),
+                    volt_vault: info(Pubkey::new_unique()),
+                    extra_volt_data: info(Pubkey::new_unique()),
+                    vault_authority: info(Pubkey::new_unique()),
+                    user_vault_tokens: &mut user_vault_tokens,
+                    pending_deposit_round_info: info(Pubkey::new_unique()),
+                    pending_deposit_round_volt_tokens: info(Pubkey::new_unique()),
+                    pending_deposit_info: info(Pubkey::new_unique()),
+                    system_program: info(Pubkey::new_unique()),
+                    token_program: info(Pubkey::new_unique()),
+                },
+                &remaining,
+            )
+            .err()
+        };
+
+        assert_eq!(
+            claim(Pubkey::new_unique()),
+            Some(error!(ErrorCode::InvalidDaoAuthority))
+        );
+        assert!(take_invoked().is_empty());
+
+        assert!(claim(dao.key).is_none());
+        let invoked = take_invoked();
+        assert_eq!(invoked.len(), 1);
+        let signer_seeds: Vec<&[u8]> = invoked[0].signer_seeds[0]
+            .iter()
+            .map(|seed| &seed[..])
+            .collect();
+        assert_eq!(signer_seeds, dao.signer_seeds());
+        assert_eq!(
+            invoked[0].instruction.accounts.last().unwrap().pubkey,
+            *remaining[0].key
+        );
+    }
+}
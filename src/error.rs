@@ -1,7 +1,18 @@
 use anchor_lang::error_code;
 
-#[error_code]
-pub enum ErrorCode {
+// declares ErrorCode and ERROR_CODES, every variant in declaration order, so ERROR_CODES[i] has error number ERROR_CODE_OFFSET + i
+macro_rules! error_codes {
+    ($( $(#[msg($msg:tt)])? $variant:ident, )*) => {
+        #[error_code]
+        pub enum ErrorCode {
+            $( $(#[msg($msg)])? $variant, )*
+        }
+
+        pub(crate) const ERROR_CODES: &[ErrorCode] = &[$(ErrorCode::$variant),*];
+    };
+}
+
+error_codes! {
     #[msg("Expiration must be in the future")]
     ExpirationIsInThePast,
     #[msg("Same quote and underlying asset, cannot create market")]
@@ -363,3 +374,18 @@ pub enum ErrorCode {
     #[msg("invalid setup rebalance entropy state")]
     InvalidSetupRebalanceEntropyState,
 }
+
+// errors of this crate's client side helpers, never returned by the volt program. the offset keeps them clear of ErrorCode
+#[error_code(offset = 9000)]
+pub enum AbiErrorCode {
+    #[msg("pp_vault (the volt's PrincipalProtectionVaultV1) must be provided for principal protection volts")]
+    PrincipalProtectionVaultNotProvided,
+}
+
+impl ErrorCode {
+    /// inverse of u32::from(ErrorCode), i.e. expects ERROR_CODE_OFFSET to be included in code
+    pub fn from_code(code: u32) -> Option<Self> {
+        let index = code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)?;
+        ERROR_CODES.get(index as usize).copied()
+    }
+}
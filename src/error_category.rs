@@ -0,0 +1,537 @@
+use crate::error::ErrorCode;
+
+/**
+ * Who can act on an ErrorCode.
+ *  NOTES:
+ *  1. User: the instruction itself is wrong (amount, accounts, signer), the user can fix and resend it
+ *  2. Timing: the instruction is valid but not in the current round phase, resending it later may succeed
+ *  3. Admin: raised by permissioned (keeper/admin) instructions or volt/market configuration, users can't act on it
+ *  4. Invariant: internal state the program never expects to reach, operators should be alerted
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCategory {
+    User,
+    Timing,
+    Admin,
+    Invariant,
+}
+
+impl ErrorCode {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ErrorCode::InvalidWithdrawalTime
+            | ErrorCode::InvalidDepositTime
+            | ErrorCode::OptionsPositionNotSettled
+            | ErrorCode::NonUnderlyingPoolsHaveAssets
+            | ErrorCode::InvalidClaimPendingState
+            | ErrorCode::InvalidClaimPendingWithdrawalState
+            | ErrorCode::RoundHasNotStarted
+            | ErrorCode::RoundMustBeEnded
+            | ErrorCode::DepositsAndWithdrawalsAreTurnedOff => ErrorCategory::Timing,
+
+            ErrorCode::ExpectedSPLTokenProgramId
+            | ErrorCode::SizeCantBeLessThanEqZero
+            | ErrorCode::UnderlyingDestMintDoesNotMatchUnderlyingAsset
+            | ErrorCode::InsufficientVaultTokens
+            | ErrorCode::InvalidMintAmount
+            | ErrorCode::InvalidDepositAmount
+            | ErrorCode::DepositPoolDoesNotMatchVoltVault
+            | ErrorCode::VaultMintDoesNotMatchUserTokenAccount
+            | ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount
+            | ErrorCode::VaultAuthorityDoesNotMatch
+            | ErrorCode::UnderlyingAssetMintDoesNotMatchVoltVault
+            | ErrorCode::VaultMintDoesNotMatchVoltVault
+            | ErrorCode::CantHaveMultiplePendingDeposits
+            | ErrorCode::CantHaveMultiplePendingWithdrawals
+            | ErrorCode::NonWhitelistedUser
+            | ErrorCode::UserIsNotSigner
+            | ErrorCode::RoundVoltTokensMintDoesNotMatchVoltVault
+            | ErrorCode::RoundUnderlyingTokensMintDoesNotMatchVoltVault
+            | ErrorCode::FeeAccountOwnerDoesNotMatch
+            | ErrorCode::FeeAccountMintDoesNotMatchDepositPool
+            | ErrorCode::VaultCapacityWouldBeExceeded
+            | ErrorCode::IndividualDepositCapacityWouldBeExceeded
+            | ErrorCode::TokenAccountOwnersDoNotMatch
+            | ErrorCode::PendingWithdrawalInfoNotInitialized
+            | ErrorCode::PendingWithdrawalDoesNotExist
+            | ErrorCode::CannotCancelPendingWithdrawalFromOldRound
+            | ErrorCode::PendingDepositInfoNotInitialized
+            | ErrorCode::PendingDepositDoesNotExist
+            | ErrorCode::CannotCancelPendingDepositFromOldRound
+            | ErrorCode::VaultDestinationDoesNotMatchVoltVault
+            | ErrorCode::InvalidWhitelistAccountVector
+            | ErrorCode::InvalidDaoAuthority
+            | ErrorCode::DaoAuthorityMustSign
+            | ErrorCode::InvalidPendingDepositKey
+            | ErrorCode::InvalidAuthorityCheck
+            | ErrorCode::InvalidVoltType
+            | ErrorCode::InstantTransfersMustBeDisabled
+            | ErrorCode::InvalidWhitelistTokenAccountMint
+            | ErrorCode::WhitelistTokenAccountOwnerIsNotUser
+            | ErrorCode::SolTransferAuthorityMustNotBeOwnedByVoltProgram
+            | ErrorCode::InsufficientCollateralForDeposit
+            | ErrorCode::SolTransferAuthorityMustBeWritableAndSigner
+            | ErrorCode::VoltMustBeOfEntropyType
+            | ErrorCode::VoltMustBeofShortOptionsType
+            | ErrorCode::DiscriminatorDoesNotMatch => ErrorCategory::User,
+
+            ErrorCode::NumberOverflow
+            | ErrorCode::VaultAuthorityMustBeVaultMintAuthority
+            | ErrorCode::VaultAuthorityMustOwnDepositPool
+            | ErrorCode::VaultAuthorityMustOwnPremiumPool
+            | ErrorCode::VoltVaulttMustOwnWriterTokenPool
+            | ErrorCode::VoltVaultMustOwnOptionPool
+            | ErrorCode::ShouldBeUnreachable
+            | ErrorCode::AccountEquityLessThanZero
+            | ErrorCode::BasePositionMustBeNegative
+            | ErrorCode::QuotePositionMustBePositive
+            | ErrorCode::NewEquityMustBeHigherThanDepositAmount
+            | ErrorCode::RoundNumberMustNotOverflow
+            | ErrorCode::VaultMintSupplyMustBeZeroIfEquityIsZero => ErrorCategory::Invariant,
+
+            ErrorCode::ExpirationIsInThePast
+            | ErrorCode::QuoteAndUnderlyingAssetMustDiffer
+            | ErrorCode::QuoteOrUnderlyingAmountCannotBe0
+            | ErrorCode::OptionMarketMustBeMintAuthority
+            | ErrorCode::OptionMarketMustOwnUnderlyingAssetPool
+            | ErrorCode::OptionMarketMustOwnQuoteAssetPool
+            | ErrorCode::MintFeeMustBeOwnedByFeeOwner
+            | ErrorCode::ExerciseFeeMustBeOwnedByFeeOwner
+            | ErrorCode::MintFeeTokenMustMatchUnderlyingAsset
+            | ErrorCode::ExerciseFeeTokenMustMatchQuoteAsset
+            | ErrorCode::OptionMarketExpiredCantMint
+            | ErrorCode::UnderlyingPoolAccountDoesNotMatchMarket
+            | ErrorCode::OptionTokenMintDoesNotMatchMarket
+            | ErrorCode::WriterTokenMintDoesNotMatchMarket
+            | ErrorCode::MintFeeKeyDoesNotMatchOptionMarket
+            | ErrorCode::ExerciseFeeKeyDoesNotMatchOptionMarket
+            | ErrorCode::QuotePoolAccountDoesNotMatchMarket
+            | ErrorCode::FeeOwnerDoesNotMatchProgram
+            | ErrorCode::OptionMarketExpiredCantExercise
+            | ErrorCode::OptionMarketNotExpiredCantClose
+            | ErrorCode::NotEnoughQuoteAssetsInPool
+            | ErrorCode::InvalidAuth
+            | ErrorCode::CoinMintIsNotOptionMint
+            | ErrorCode::CannotPruneActiveMarket
+            | ErrorCode::InvalidOrderType
+            | ErrorCode::InvalidSelfTradeBehavior
+            | ErrorCode::Unauthorized
+            | ErrorCode::InsufficientCollateralForWriting
+            | ErrorCode::ContractExpired
+            | ErrorCode::ContractNotYetExpired
+            | ErrorCode::InvalidRebalanceExitTime
+            | ErrorCode::InvalidRebalanceEntryTime
+            | ErrorCode::InvalidRebalancePrepareTime
+            | ErrorCode::InvalidSetNextOptionTime
+            | ErrorCode::InvalidRebalanceSettleTime
+            | ErrorCode::InvalidRebalanceSettleState
+            | ErrorCode::InvalidRebalanceEnterState
+            | ErrorCode::OptionPoolDoesNotMatchVoltVault
+            | ErrorCode::PremiumPoolDoesNotMatchVoltVault
+            | ErrorCode::TraidngPoolDoesNotMatchVoltVault
+            | ErrorCode::OptionMintDoesNotMatchOptionMarket
+            | ErrorCode::NoOrdersInOptionOrderBook
+            | ErrorCode::CpiProgramMustBeSomeInPlaceOrder
+            | ErrorCode::NewOptionMustNotBeExpired
+            | ErrorCode::NewOptionMustHaveExactExpiry
+            | ErrorCode::NewOptionHasWrongUnderlyingAsset
+            | ErrorCode::NewOptionHasWrongQuoteAsset
+            | ErrorCode::NewOptionHasWrongContractSize
+            | ErrorCode::NewOptionHasInvalidStrike
+            | ErrorCode::RebalanceSettleHasLeftoverWriterTokens
+            | ErrorCode::CurrentOptionMustNotBeExpired
+            | ErrorCode::CannotReinitializeVolt
+            | ErrorCode::OldOptionAndWriterTokenPoolsMustBeEmpty
+            | ErrorCode::InvalidOldOptionWriterTokenPools
+            | ErrorCode::DexProgramIdDoesNotMatchAnchor
+            | ErrorCode::InertiaProgramIdDoesNotMatch
+            | ErrorCode::InvalidAuthorityForPermissionedInstruction
+            | ErrorCode::WriterTokenMintDoesNotMatchOptionMarket
+            | ErrorCode::OptionMarketMustBeOwnedByProtocol
+            | ErrorCode::QuoteAssetMintDoesNotMatchVoltVault
+            | ErrorCode::OptionMarketDoesNotMatchVoltVault
+            | ErrorCode::WriterTokenPoolDoesNotMatchVoltVault
+            | ErrorCode::InvalidRebalanceSwapPremiumState
+            | ErrorCode::InvalidStartRoundState
+            | ErrorCode::InvalidSetNextOptionState
+            | ErrorCode::InvalidEndRoundState
+            | ErrorCode::InvalidNextOptionMarket
+            | ErrorCode::TokenNotRevoked
+            | ErrorCode::InvalidWhitelistAuthority
+            | ErrorCode::InvalidWhitelistAndOptionMarketCombination
+            | ErrorCode::UnderlyingAssetPoolDoesNotMatchOptionMarket
+            | ErrorCode::NoOppositeOrderOnSerumMarket
+            | ErrorCode::BidPriceOnSerumMarketTooLow
+            | ErrorCode::OfferPriceOnSerumMarketTooHigh
+            | ErrorCode::UnderlyingOpenOrdersDoesNotMatchVoltVault
+            | ErrorCode::MustHaveAtLeastOneMarketMakerAccessToken
+            | ErrorCode::MiddlewareProgramIdDoesNotMatch
+            | ErrorCode::UnsupportedOptionMarketProgramId
+            | ErrorCode::InvalidEndDcaRoundState
+            | ErrorCode::PermissionedMarketPremiumPoolDoesNotMatchVoltVault
+            | ErrorCode::InvalidPermissionedMarketPremiumMint
+            | ErrorCode::PremiumPoolAmountMustBeGreaterThanZero
+            | ErrorCode::CantCloseNonEmptyTokenAccount
+            | ErrorCode::MustFinishEnteringBeforeSettlingPermissionedMarketPremium
+            | ErrorCode::MustTakeWithdrawalFeesBeforeStartingRound
+            | ErrorCode::MustNotHaveSoldOptionTokens
+            | ErrorCode::CantCloseAccountUnlessEmpty
+            | ErrorCode::OpenOrderMustBeEmptyToClose
+            | ErrorCode::InvalidDaoProgramId
+            | ErrorCode::VoltMustBeForDao
+            | ErrorCode::InvalidEndEntropyRoundState
+            | ErrorCode::CantFindPerpMarketIndex
+            | ErrorCode::QuotePositionChangedTooMuch
+            | ErrorCode::MustMoveCloserToTargetCollateralization
+            | ErrorCode::CollateralNotWithinLenience
+            | ErrorCode::InvalidRebalanceEntropyState
+            | ErrorCode::TargetCollateralRatioMustBeNegative
+            | ErrorCode::RebalanceMustBeReady
+            | ErrorCode::IncorrectHedge
+            | ErrorCode::VaultNameMustBeNonZeroLength
+            | ErrorCode::VaultDoesNotSupportOverLeveragedStrategies
+            | ErrorCode::LenienceMustBeGreaterThanZero
+            | ErrorCode::LenienceShouldNotBeGreaterThanLeverage
+            | ErrorCode::HedgeLenienceMustBeGreaterThanZero
+            | ErrorCode::VaultDoesNotSupportExitEarlyOverLeveragedStrategies
+            | ErrorCode::SoloptionsProgramIdDoesNotMatch
+            | ErrorCode::UnrecognizedEntropyProgramId
+            | ErrorCode::InvalidTakePerformanceFeesState
+            | ErrorCode::RealizedOraclePriceTooFarOffClientProvided
+            | ErrorCode::InvalidSetupRebalanceEntropyState => ErrorCategory::Admin,
+        }
+    }
+
+    /// true if resending the same instruction later (w/o changing it) may succeed,
+    /// either because the round phase moves on or because market conditions change
+    pub fn is_retryable_later(&self) -> bool {
+        self.category() == ErrorCategory::Timing
+            || matches!(
+                self,
+                ErrorCode::InvalidRebalanceExitTime
+                    | ErrorCode::InvalidRebalanceEntryTime
+                    | ErrorCode::InvalidRebalancePrepareTime
+                    | ErrorCode::InvalidSetNextOptionTime
+                    | ErrorCode::InvalidRebalanceSettleTime
+                    | ErrorCode::ContractNotYetExpired
+                    | ErrorCode::OptionMarketNotExpiredCantClose
+                    | ErrorCode::NoOrdersInOptionOrderBook
+                    | ErrorCode::NoOppositeOrderOnSerumMarket
+                    | ErrorCode::BidPriceOnSerumMarketTooLow
+                    | ErrorCode::OfferPriceOnSerumMarketTooHigh
+                    | ErrorCode::QuotePositionChangedTooMuch
+                    | ErrorCode::RealizedOraclePriceTooFarOffClientProvided
+            )
+    }
+
+    /// short user-facing hint on how to resolve the error
+    pub fn remediation(&self) -> &'static str {
+        match self {
+            ///////// timing ///////////
+            ErrorCode::InvalidWithdrawalTime
+            | ErrorCode::InvalidDepositTime
+            | ErrorCode::OptionsPositionNotSettled
+            | ErrorCode::NonUnderlyingPoolsHaveAssets => {
+                "the volt is rebalancing, try again once the rebalance has completed"
+            }
+            ErrorCode::RoundHasNotStarted => "wait for the next round to start",
+            ErrorCode::InvalidClaimPendingState
+            | ErrorCode::InvalidClaimPendingWithdrawalState
+            | ErrorCode::RoundMustBeEnded => {
+                "the round this request was made in has not ended yet, claim once it has"
+            }
+            ErrorCode::DepositsAndWithdrawalsAreTurnedOff => {
+                "deposits and withdrawals are paused for this volt, try again later"
+            }
+
+            ///////// user ///////////
+            ErrorCode::SizeCantBeLessThanEqZero
+            | ErrorCode::InvalidMintAmount
+            | ErrorCode::InvalidDepositAmount => "use an amount greater than zero",
+            ErrorCode::InsufficientVaultTokens | ErrorCode::InsufficientCollateralForDeposit => {
+                "the amount exceeds the available balance, use a smaller amount"
+            }
+            ErrorCode::VaultCapacityWouldBeExceeded => {
+                "the volt is at capacity, deposit a smaller amount or wait for capacity to free up"
+            }
+            ErrorCode::IndividualDepositCapacityWouldBeExceeded => {
+                "the per user deposit limit would be exceeded, deposit a smaller amount"
+            }
+            ErrorCode::CantHaveMultiplePendingDeposits
+            | ErrorCode::CantHaveMultiplePendingWithdrawals => {
+                "claim the pending request from a previous round first, or use the *_with_claim instruction"
+            }
+            ErrorCode::PendingWithdrawalInfoNotInitialized
+            | ErrorCode::PendingWithdrawalDoesNotExist
+            | ErrorCode::PendingDepositInfoNotInitialized
+            | ErrorCode::PendingDepositDoesNotExist => "there is no pending request to claim or cancel",
+            ErrorCode::CannotCancelPendingWithdrawalFromOldRound
+            | ErrorCode::CannotCancelPendingDepositFromOldRound => {
+                "the round this request was made in has ended, claim it instead of cancelling"
+            }
+            ErrorCode::NonWhitelistedUser => {
+                "this volt is restricted to whitelisted users, ask the volt's admin for access"
+            }
+            ErrorCode::InvalidWhitelistAccountVector
+            | ErrorCode::InvalidWhitelistTokenAccountMint
+            | ErrorCode::WhitelistTokenAccountOwnerIsNotUser => {
+                "pass the volt's whitelist account and a whitelist token account owned by the user"
+            }
+            ErrorCode::UserIsNotSigner
+            | ErrorCode::DaoAuthorityMustSign
+            | ErrorCode::SolTransferAuthorityMustBeWritableAndSigner => {
+                "the authority must sign the transaction"
+            }
+            ErrorCode::InvalidDaoAuthority | ErrorCode::InvalidAuthorityCheck => {
+                "authority_check must be the signing authority (or the dao authority for dao volts)"
+            }
+            ErrorCode::InvalidVoltType
+            | ErrorCode::VoltMustBeOfEntropyType
+            | ErrorCode::VoltMustBeofShortOptionsType => {
+                "this instruction is not supported by this volt's type"
+            }
+            ErrorCode::InstantTransfersMustBeDisabled => {
+                "instant transfers are not enabled for this volt, use a regular deposit or withdrawal"
+            }
+            ErrorCode::ExpectedSPLTokenProgramId
+            | ErrorCode::UnderlyingDestMintDoesNotMatchUnderlyingAsset
+            | ErrorCode::DepositPoolDoesNotMatchVoltVault
+            | ErrorCode::VaultMintDoesNotMatchUserTokenAccount
+            | ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount
+            | ErrorCode::VaultAuthorityDoesNotMatch
+            | ErrorCode::UnderlyingAssetMintDoesNotMatchVoltVault
+            | ErrorCode::VaultMintDoesNotMatchVoltVault
+            | ErrorCode::RoundVoltTokensMintDoesNotMatchVoltVault
+            | ErrorCode::RoundUnderlyingTokensMintDoesNotMatchVoltVault
+            | ErrorCode::FeeAccountOwnerDoesNotMatch
+            | ErrorCode::FeeAccountMintDoesNotMatchDepositPool
+            | ErrorCode::TokenAccountOwnersDoNotMatch
+            | ErrorCode::VaultDestinationDoesNotMatchVoltVault
+            | ErrorCode::InvalidPendingDepositKey
+            | ErrorCode::SolTransferAuthorityMustNotBeOwnedByVoltProgram
+            | ErrorCode::DiscriminatorDoesNotMatch => {
+                "an account does not belong to this volt or user, rebuild the instruction from the volt's current state"
+            }
+
+            ///////// invariant ///////////
+            ErrorCode::NumberOverflow
+            | ErrorCode::VaultAuthorityMustBeVaultMintAuthority
+            | ErrorCode::VaultAuthorityMustOwnDepositPool
+            | ErrorCode::VaultAuthorityMustOwnPremiumPool
+            | ErrorCode::VoltVaulttMustOwnWriterTokenPool
+            | ErrorCode::VoltVaultMustOwnOptionPool
+            | ErrorCode::ShouldBeUnreachable
+            | ErrorCode::AccountEquityLessThanZero
+            | ErrorCode::BasePositionMustBeNegative
+            | ErrorCode::QuotePositionMustBePositive
+            | ErrorCode::NewEquityMustBeHigherThanDepositAmount
+            | ErrorCode::RoundNumberMustNotOverflow
+            | ErrorCode::VaultMintSupplyMustBeZeroIfEquityIsZero => {
+                "the volt is in an unexpected state, contact the volt's operators"
+            }
+
+            ///////// admin ///////////
+            ErrorCode::InvalidRebalanceExitTime
+            | ErrorCode::InvalidRebalanceEntryTime
+            | ErrorCode::InvalidRebalancePrepareTime
+            | ErrorCode::InvalidSetNextOptionTime
+            | ErrorCode::InvalidRebalanceSettleTime
+            | ErrorCode::InvalidRebalanceSettleState
+            | ErrorCode::InvalidRebalanceEnterState
+            | ErrorCode::InvalidRebalanceSwapPremiumState
+            | ErrorCode::InvalidStartRoundState
+            | ErrorCode::InvalidSetNextOptionState
+            | ErrorCode::InvalidEndRoundState
+            | ErrorCode::InvalidEndDcaRoundState
+            | ErrorCode::MustFinishEnteringBeforeSettlingPermissionedMarketPremium
+            | ErrorCode::MustTakeWithdrawalFeesBeforeStartingRound
+            | ErrorCode::InvalidEndEntropyRoundState
+            | ErrorCode::InvalidRebalanceEntropyState
+            | ErrorCode::RebalanceMustBeReady
+            | ErrorCode::InvalidTakePerformanceFeesState
+            | ErrorCode::InvalidSetupRebalanceEntropyState => {
+                "keeper instruction sent out of order, check the volt's current RoundPhase"
+            }
+            ErrorCode::NoOrdersInOptionOrderBook
+            | ErrorCode::NoOppositeOrderOnSerumMarket
+            | ErrorCode::BidPriceOnSerumMarketTooLow
+            | ErrorCode::OfferPriceOnSerumMarketTooHigh
+            | ErrorCode::QuotePositionChangedTooMuch
+            | ErrorCode::MustMoveCloserToTargetCollateralization
+            | ErrorCode::CollateralNotWithinLenience
+            | ErrorCode::IncorrectHedge
+            | ErrorCode::RealizedOraclePriceTooFarOffClientProvided => {
+                "market conditions did not allow the trade, retry with updated prices or sizes"
+            }
+            ErrorCode::InvalidAuth
+            | ErrorCode::Unauthorized
+            | ErrorCode::InvalidAuthorityForPermissionedInstruction
+            | ErrorCode::TokenNotRevoked
+            | ErrorCode::InvalidWhitelistAuthority
+            | ErrorCode::MustHaveAtLeastOneMarketMakerAccessToken
+            | ErrorCode::InvalidDaoProgramId
+            | ErrorCode::VoltMustBeForDao => {
+                "only the volt's admin or keeper may send this instruction"
+            }
+            ErrorCode::PremiumPoolAmountMustBeGreaterThanZero
+            | ErrorCode::CantCloseNonEmptyTokenAccount
+            | ErrorCode::CantCloseAccountUnlessEmpty
+            | ErrorCode::OpenOrderMustBeEmptyToClose
+            | ErrorCode::OldOptionAndWriterTokenPoolsMustBeEmpty
+            | ErrorCode::RebalanceSettleHasLeftoverWriterTokens
+            | ErrorCode::MustNotHaveSoldOptionTokens => {
+                "token or open orders balances are not as expected, settle or empty them first"
+            }
+            ErrorCode::CannotReinitializeVolt
+            | ErrorCode::TargetCollateralRatioMustBeNegative
+            | ErrorCode::VaultNameMustBeNonZeroLength
+            | ErrorCode::VaultDoesNotSupportOverLeveragedStrategies
+            | ErrorCode::LenienceMustBeGreaterThanZero
+            | ErrorCode::LenienceShouldNotBeGreaterThanLeverage
+            | ErrorCode::HedgeLenienceMustBeGreaterThanZero
+            | ErrorCode::VaultDoesNotSupportExitEarlyOverLeveragedStrategies => {
+                "invalid volt parameters, fix the arguments of the admin instruction"
+            }
+            ErrorCode::ExpirationIsInThePast
+            | ErrorCode::QuoteAndUnderlyingAssetMustDiffer
+            | ErrorCode::QuoteOrUnderlyingAmountCannotBe0
+            | ErrorCode::OptionMarketMustBeMintAuthority
+            | ErrorCode::OptionMarketMustOwnUnderlyingAssetPool
+            | ErrorCode::OptionMarketMustOwnQuoteAssetPool
+            | ErrorCode::MintFeeMustBeOwnedByFeeOwner
+            | ErrorCode::ExerciseFeeMustBeOwnedByFeeOwner
+            | ErrorCode::MintFeeTokenMustMatchUnderlyingAsset
+            | ErrorCode::ExerciseFeeTokenMustMatchQuoteAsset
+            | ErrorCode::OptionMarketExpiredCantMint
+            | ErrorCode::UnderlyingPoolAccountDoesNotMatchMarket
+            | ErrorCode::OptionTokenMintDoesNotMatchMarket
+            | ErrorCode::WriterTokenMintDoesNotMatchMarket
+            | ErrorCode::MintFeeKeyDoesNotMatchOptionMarket
+            | ErrorCode::ExerciseFeeKeyDoesNotMatchOptionMarket
+            | ErrorCode::QuotePoolAccountDoesNotMatchMarket
+            | ErrorCode::FeeOwnerDoesNotMatchProgram
+            | ErrorCode::OptionMarketExpiredCantExercise
+            | ErrorCode::OptionMarketNotExpiredCantClose
+            | ErrorCode::NotEnoughQuoteAssetsInPool
+            | ErrorCode::CoinMintIsNotOptionMint
+            | ErrorCode::CannotPruneActiveMarket
+            | ErrorCode::InvalidOrderType
+            | ErrorCode::InvalidSelfTradeBehavior
+            | ErrorCode::InsufficientCollateralForWriting
+            | ErrorCode::ContractExpired
+            | ErrorCode::ContractNotYetExpired
+            | ErrorCode::OptionPoolDoesNotMatchVoltVault
+            | ErrorCode::PremiumPoolDoesNotMatchVoltVault
+            | ErrorCode::TraidngPoolDoesNotMatchVoltVault
+            | ErrorCode::OptionMintDoesNotMatchOptionMarket
+            | ErrorCode::CpiProgramMustBeSomeInPlaceOrder
+            | ErrorCode::NewOptionMustNotBeExpired
+            | ErrorCode::NewOptionMustHaveExactExpiry
+            | ErrorCode::NewOptionHasWrongUnderlyingAsset
+            | ErrorCode::NewOptionHasWrongQuoteAsset
+            | ErrorCode::NewOptionHasWrongContractSize
+            | ErrorCode::NewOptionHasInvalidStrike
+            | ErrorCode::CurrentOptionMustNotBeExpired
+            | ErrorCode::InvalidOldOptionWriterTokenPools
+            | ErrorCode::DexProgramIdDoesNotMatchAnchor
+            | ErrorCode::InertiaProgramIdDoesNotMatch
+            | ErrorCode::WriterTokenMintDoesNotMatchOptionMarket
+            | ErrorCode::OptionMarketMustBeOwnedByProtocol
+            | ErrorCode::QuoteAssetMintDoesNotMatchVoltVault
+            | ErrorCode::OptionMarketDoesNotMatchVoltVault
+            | ErrorCode::WriterTokenPoolDoesNotMatchVoltVault
+            | ErrorCode::InvalidNextOptionMarket
+            | ErrorCode::InvalidWhitelistAndOptionMarketCombination
+            | ErrorCode::UnderlyingAssetPoolDoesNotMatchOptionMarket
+            | ErrorCode::UnderlyingOpenOrdersDoesNotMatchVoltVault
+            | ErrorCode::MiddlewareProgramIdDoesNotMatch
+            | ErrorCode::UnsupportedOptionMarketProgramId
+            | ErrorCode::PermissionedMarketPremiumPoolDoesNotMatchVoltVault
+            | ErrorCode::InvalidPermissionedMarketPremiumMint
+            | ErrorCode::CantFindPerpMarketIndex
+            | ErrorCode::SoloptionsProgramIdDoesNotMatch
+            | ErrorCode::UnrecognizedEntropyProgramId => {
+                "option market, trading or program accounts do not match the volt's configuration"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ERROR_CODES;
+
+    #[test]
+    fn from_code_round_trips() {
+        for (i, error_code) in ERROR_CODES.iter().enumerate() {
+            let code = u32::from(*error_code);
+            assert_eq!(
+                code,
+                anchor_lang::error::ERROR_CODE_OFFSET + i as u32,
+                "{:?}",
+                error_code
+            );
+            assert_eq!(ErrorCode::from_code(code).map(u32::from), Some(code));
+        }
+        assert!(ErrorCode::from_code(anchor_lang::error::ERROR_CODE_OFFSET - 1).is_none());
+        assert!(ErrorCode::from_code(
+            anchor_lang::error::ERROR_CODE_OFFSET + ERROR_CODES.len() as u32
+        )
+        .is_none());
+        assert!(ErrorCode::from_code(0).is_none());
+
+        // the table is generated with the enum, messages survive the macro
+        assert!(matches!(
+            ERROR_CODES.last(),
+            Some(ErrorCode::InvalidSetupRebalanceEntropyState)
+        ));
+        assert_eq!(ErrorCode::InvalidVoltType.to_string(), "invalid volt type");
+    }
+
+    #[test]
+    fn categories() {
+        assert_eq!(
+            ErrorCode::InvalidDepositAmount.category(),
+            ErrorCategory::User
+        );
+        assert_eq!(
+            ErrorCode::VaultCapacityWouldBeExceeded.category(),
+            ErrorCategory::User
+        );
+        assert_eq!(
+            ErrorCode::RoundHasNotStarted.category(),
+            ErrorCategory::Timing
+        );
+        assert_eq!(
+            ErrorCode::InvalidRebalanceEnterState.category(),
+            ErrorCategory::Admin
+        );
+        assert_eq!(
+            ErrorCode::ShouldBeUnreachable.category(),
+            ErrorCategory::Invariant
+        );
+    }
+
+    #[test]
+    fn retryable_later() {
+        assert!(ErrorCode::InvalidDepositTime.is_retryable_later());
+        assert!(ErrorCode::NoOppositeOrderOnSerumMarket.is_retryable_later());
+        assert!(!ErrorCode::InvalidDepositAmount.is_retryable_later());
+        assert!(!ErrorCode::ShouldBeUnreachable.is_retryable_later());
+
+        // every timing error is retryable
+        for error_code in ERROR_CODES {
+            if error_code.category() == ErrorCategory::Timing {
+                assert!(error_code.is_retryable_later(), "{:?}", error_code);
+            }
+        }
+    }
+
+    #[test]
+    fn every_error_has_a_remediation() {
+        for error_code in ERROR_CODES {
+            assert!(!error_code.remediation().is_empty(), "{:?}", error_code);
+        }
+    }
+}
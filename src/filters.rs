@@ -0,0 +1,310 @@
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::layout::{HasLayout, DISCRIMINATOR_LEN};
+use crate::{ExtraVoltData, PendingDeposit, PendingWithdrawal, VoltType, VoltVault};
+
+/**
+ * getProgramAccounts filter, mirrors RpcFilterType of solana-client w/o depending on it.
+ * e.g RpcFilterType::Memcmp(Memcmp { offset, bytes: MemcmpEncodedBytes::Bytes(bytes), encoding: None })
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccountFilter {
+    DataSize(u64),
+    Memcmp { offset: usize, bytes: Vec<u8> },
+}
+
+impl AccountFilter {
+    // evaluates the filter like the RPC node would, e.g to re-check accounts from a websocket subscription
+    pub fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            AccountFilter::DataSize(size) => data.len() as u64 == *size,
+            AccountFilter::Memcmp { offset, bytes } => data
+                .get(*offset..offset + bytes.len())
+                .is_some_and(|slice| slice == bytes.as_slice()),
+        }
+    }
+}
+
+// range of a u64 field. memcmp can't express it, so it's only checked client-side by AccountFilters::matches
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeFilter {
+    pub offset: usize,
+    pub range: RangeInclusive<u64>,
+}
+
+impl RangeFilter {
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.get(self.offset..self.offset + 8)
+            .map(|slice| u64::from_le_bytes(slice.try_into().unwrap()))
+            .is_some_and(|value| self.range.contains(&value))
+    }
+}
+
+/**
+ * Filters for program accounts of type T, using the field offsets in T::LAYOUT.
+ *  NOTES:
+ *  1. new() only matches the anchor discriminator. data_size() additionally matches DISCRIMINATOR_LEN + LEN,
+ *     which the volt program allocates for pending deposits/withdrawals and round accounts, but not necessarily for older volts
+ *  2. memcmp only matches equal bytes. range filters (e.g "round_number != 0" for pending accounts, see for_rounds()) aren't sent to the RPC node,
+ *     filters()/into_filters() only return the query, check the returned accounts with matches()
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountFilters<T> {
+    filters: Vec<AccountFilter>,
+    ranges: Vec<RangeFilter>,
+    account: PhantomData<T>,
+}
+
+impl<T: Discriminator + HasLayout> AccountFilters<T> {
+    pub fn new() -> Self {
+        AccountFilters {
+            filters: vec![AccountFilter::Memcmp {
+                offset: 0,
+                bytes: T::discriminator().to_vec(),
+            }],
+            ranges: Vec::new(),
+            account: PhantomData,
+        }
+    }
+
+    pub fn data_size(mut self) -> Self {
+        self.filters.push(AccountFilter::DataSize(
+            (DISCRIMINATOR_LEN + T::LAYOUT.len) as u64,
+        ));
+        self
+    }
+
+    fn field_eq(mut self, name: &str, bytes: &[u8]) -> Self {
+        let field = T::LAYOUT
+            .field(name)
+            .unwrap_or_else(|| panic!("{} has no field {}", T::LAYOUT.name, name));
+        assert_eq!(field.size, bytes.len(), "size of {}", name);
+        self.filters.push(AccountFilter::Memcmp {
+            offset: field.offset,
+            bytes: bytes.to_vec(),
+        });
+        self
+    }
+
+    fn field_in_range(mut self, name: &str, range: RangeInclusive<u64>) -> Self {
+        let field = T::LAYOUT
+            .field(name)
+            .unwrap_or_else(|| panic!("{} has no field {}", T::LAYOUT.name, name));
+        assert_eq!(field.ty, "u64", "type of {}", name);
+        self.ranges.push(RangeFilter {
+            offset: field.offset,
+            range,
+        });
+        self
+    }
+
+    pub fn filters(&self) -> &[AccountFilter] {
+        &self.filters
+    }
+
+    pub fn into_filters(self) -> Vec<AccountFilter> {
+        self.filters
+    }
+
+    pub fn ranges(&self) -> &[RangeFilter] {
+        &self.ranges
+    }
+
+    // the query and the client-side range filters
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.filters.iter().all(|filter| filter.matches(data))
+            && self.ranges.iter().all(|range| range.matches(data))
+    }
+}
+
+impl<T: Discriminator + HasLayout> Default for AccountFilters<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccountFilters<VoltVault> {
+    pub fn underlying_asset_mint(self, mint: &Pubkey) -> Self {
+        self.field_eq("underlying_asset_mint", mint.as_ref())
+    }
+
+    pub fn quote_asset_mint(self, mint: &Pubkey) -> Self {
+        self.field_eq("quote_asset_mint", mint.as_ref())
+    }
+
+    pub fn vault_type(self, volt_type: VoltType) -> Self {
+        self.field_eq("vault_type", &u64::from(volt_type).to_le_bytes())
+    }
+
+    pub fn admin_key(self, admin_key: &Pubkey) -> Self {
+        self.field_eq("admin_key", admin_key.as_ref())
+    }
+
+    // set when serum markets are closed and expiration is validated
+    pub fn have_taken_withdrawal_fees(self, have_taken_withdrawal_fees: bool) -> Self {
+        self.field_eq(
+            "have_taken_withdrawal_fees",
+            &[have_taken_withdrawal_fees as u8],
+        )
+    }
+}
+
+impl AccountFilters<ExtraVoltData> {
+    pub fn is_for_dao(self, is_for_dao: bool) -> Self {
+        self.field_eq("is_for_dao", &[is_for_dao as u8])
+    }
+
+    pub fn dao_program_id(self, dao_program_id: &Pubkey) -> Self {
+        self.field_eq("dao_program_id", dao_program_id.as_ref())
+    }
+}
+
+impl AccountFilters<PendingDeposit> {
+    pub fn round_number(self, round_number: u64) -> Self {
+        self.field_eq("round_number", &round_number.to_le_bytes())
+    }
+
+    // a single query for the pending deposits of all volts, the rounds are checked by matches(). e.g for_rounds(1..=u64::MAX) for all existing ones.
+    // the account doesn't store its volt, compare addresses against find_pending_deposit_address to tie results to a volt
+    pub fn for_rounds(rounds: RangeInclusive<u64>) -> Self {
+        Self::new()
+            .data_size()
+            .field_in_range("round_number", rounds)
+    }
+}
+
+impl AccountFilters<PendingWithdrawal> {
+    pub fn round_number(self, round_number: u64) -> Self {
+        self.field_eq("round_number", &round_number.to_le_bytes())
+    }
+
+    // a single query for the pending withdrawals of all volts, the rounds are checked by matches(). e.g for_rounds(1..=u64::MAX) for all existing ones.
+    // the account doesn't store its volt, compare addresses against find_pending_withdrawal_address to tie results to a volt
+    pub fn for_rounds(rounds: RangeInclusive<u64>) -> Self {
+        Self::new()
+            .data_size()
+            .field_in_range("round_number", rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_deposit_data(round_number: u64) -> Vec<u8> {
+        let mut data = Vec::new();
+        PendingDeposit {
+            initialized: true,
+            round_number,
+            num_underlying_deposited: 1_000,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        data
+    }
+
+    #[test]
+    fn for_rounds_is_a_single_query() {
+        let filters = AccountFilters::<PendingDeposit>::for_rounds(1..=u64::MAX);
+        assert_eq!(
+            filters.filters(),
+            &[
+                AccountFilter::Memcmp {
+                    offset: 0,
+                    bytes: PendingDeposit::discriminator().to_vec(),
+                },
+                AccountFilter::DataSize(8 + 17),
+            ]
+        );
+        assert_eq!(
+            filters.ranges(),
+            &[RangeFilter {
+                offset: 9,
+                range: 1..=u64::MAX,
+            }]
+        );
+    }
+
+    #[test]
+    fn for_rounds_matches_rounds_client_side() {
+        let filters = AccountFilters::<PendingDeposit>::for_rounds(1..=u64::MAX);
+        assert!(!filters.matches(&pending_deposit_data(0)));
+        assert!(filters.matches(&pending_deposit_data(1)));
+        assert!(filters.matches(&pending_deposit_data(u64::MAX)));
+
+        let filters = AccountFilters::<PendingDeposit>::for_rounds(2..=3);
+        assert!(!filters.matches(&pending_deposit_data(1)));
+        assert!(filters.matches(&pending_deposit_data(3)));
+        assert!(!filters.matches(&pending_deposit_data(4)));
+        // the query still matches, the range is not sent to the RPC node
+        assert!(filters
+            .filters()
+            .iter()
+            .all(|filter| filter.matches(&pending_deposit_data(4))));
+
+        // same size, other discriminator
+        let mut data = Vec::new();
+        PendingWithdrawal {
+            initialized: true,
+            round_number: 3,
+            num_volt_redeemed: 1_000,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        assert!(!filters.matches(&data));
+        assert!(AccountFilters::<PendingWithdrawal>::for_rounds(2..=3).matches(&data));
+    }
+
+    #[test]
+    fn short_data_does_not_match() {
+        let data = pending_deposit_data(3);
+        let filters = AccountFilters::<PendingDeposit>::new().round_number(3);
+        assert!(filters.matches(&data));
+        assert!(!filters.matches(&data[..12]));
+        assert!(!RangeFilter {
+            offset: 9,
+            range: 0..=u64::MAX,
+        }
+        .matches(&data[..16]));
+        assert!(!AccountFilter::DataSize(25).matches(&data[..24]));
+    }
+
+    #[test]
+    fn volt_vault_field_filters() {
+        let mint = Pubkey::new_unique();
+        let volt_vault = VoltVault {
+            underlying_asset_mint: mint,
+            vault_type: VoltType::ENTROPY,
+            have_taken_withdrawal_fees: true,
+            ..VoltVault::default()
+        };
+        let mut data = Vec::new();
+        volt_vault.try_serialize(&mut data).unwrap();
+
+        let filters = AccountFilters::<VoltVault>::new()
+            .data_size()
+            .underlying_asset_mint(&mint)
+            .vault_type(VoltType::Entropy)
+            .have_taken_withdrawal_fees(true);
+        assert_eq!(
+            filters.filters()[2],
+            AccountFilter::Memcmp {
+                offset: 8 + 321,
+                bytes: mint.to_bytes().to_vec(),
+            }
+        );
+        assert!(filters.matches(&data));
+        assert!(!filters
+            .clone()
+            .quote_asset_mint(&Pubkey::new_unique())
+            .matches(&data));
+        assert!(!AccountFilters::<VoltVault>::new()
+            .vault_type(VoltType::ShortOptions)
+            .matches(&data));
+        assert!(!AccountFilters::<ExtraVoltData>::new().matches(&data));
+    }
+}
@@ -0,0 +1,560 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+// On-chain helpers for programs that CPI into the volt program.
+// NOTES:
+//  1. each helper builds the crate::cpi::accounts struct, invokes the instruction and reloads the caller's token accounts the instruction transfers to/from
+//  2. signer_seeds are passed to CpiContext::new_with_signer as is. pass &[] if the authorities signed the outer transaction
+//  3. pass-through accounts are AccountInfos (use .to_account_info() on the caller's fields), so pending deposit/withdrawal accounts may be uninitialized
+//  4. remaining_accounts are appended to every instruction as is. deposits into entropy and principal protection volts
+//     need the volt type's accounts, see VoltRemainingAccounts::to_account_infos
+
+pub struct DepositAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub payer_authority: AccountInfo<'info>,
+    pub non_payer_authority: AccountInfo<'info>,
+    pub authority_check: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub whitelist: AccountInfo<'info>,
+    pub deposit_pool: AccountInfo<'info>,
+    pub writer_token_pool: AccountInfo<'info>,
+    pub user_vault_tokens: &'a mut Account<'info, TokenAccount>,
+    pub user_ul_tokens: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub round_volt_tokens: AccountInfo<'info>,
+    pub round_underlying_tokens: AccountInfo<'info>,
+    pub pending_deposit_info: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn deposit<'info>(
+    accounts: DepositAccounts<'_, 'info>,
+    deposit_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::Deposit {
+        payer_authority: accounts.payer_authority,
+        non_payer_authority: accounts.non_payer_authority,
+        authority_check: accounts.authority_check,
+        vault_mint: accounts.vault_mint,
+        volt_vault: accounts.volt_vault,
+        vault_authority: accounts.vault_authority,
+        extra_volt_data: accounts.extra_volt_data,
+        whitelist: accounts.whitelist,
+        deposit_pool: accounts.deposit_pool,
+        writer_token_pool: accounts.writer_token_pool,
+        user_vault_tokens: accounts.user_vault_tokens.to_account_info(),
+        user_ul_tokens: accounts.user_ul_tokens.to_account_info(),
+        round_info: accounts.round_info,
+        round_volt_tokens: accounts.round_volt_tokens,
+        round_underlying_tokens: accounts.round_underlying_tokens,
+        pending_deposit_info: accounts.pending_deposit_info,
+        epoch_info: accounts.epoch_info,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::deposit(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+        deposit_amount,
+    )?;
+    accounts.user_vault_tokens.reload()?;
+    accounts.user_ul_tokens.reload()?;
+    Ok(())
+}
+
+pub struct WithdrawAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub payer_authority: AccountInfo<'info>,
+    pub non_payer_authority: AccountInfo<'info>,
+    pub authority_check: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub whitelist: AccountInfo<'info>,
+    pub deposit_pool: AccountInfo<'info>,
+    pub underlying_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub vault_token_source: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub round_underlying_tokens: AccountInfo<'info>,
+    pub pending_withdrawal_info: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub fee_acct: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+pub fn withdraw<'info>(
+    accounts: WithdrawAccounts<'_, 'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::Withdraw {
+        payer_authority: accounts.payer_authority,
+        non_payer_authority: accounts.non_payer_authority,
+        authority_check: accounts.authority_check,
+        vault_mint: accounts.vault_mint,
+        volt_vault: accounts.volt_vault,
+        extra_volt_data: accounts.extra_volt_data,
+        vault_authority: accounts.vault_authority,
+        whitelist: accounts.whitelist,
+        deposit_pool: accounts.deposit_pool,
+        underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        vault_token_source: accounts.vault_token_source.to_account_info(),
+        round_info: accounts.round_info,
+        round_underlying_tokens: accounts.round_underlying_tokens,
+        pending_withdrawal_info: accounts.pending_withdrawal_info,
+        epoch_info: accounts.epoch_info,
+        fee_acct: accounts.fee_acct,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+        rent: accounts.rent,
+    };
+    crate::cpi::withdraw(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+        amount,
+    )?;
+    accounts.underlying_token_destination.reload()?;
+    accounts.vault_token_source.reload()?;
+    Ok(())
+}
+
+pub struct DepositWithClaimAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub payer_authority: AccountInfo<'info>,
+    pub non_payer_authority: AccountInfo<'info>,
+    pub sol_transfer_authority: AccountInfo<'info>,
+    pub authority_check: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub deposit_pool: AccountInfo<'info>,
+    pub writer_token_pool: AccountInfo<'info>,
+    pub vault_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub underlying_token_source: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub round_underlying_tokens: AccountInfo<'info>,
+    pub pending_deposit_info: AccountInfo<'info>,
+    pub pending_deposit_round_info: AccountInfo<'info>,
+    pub pending_deposit_round_volt_tokens: AccountInfo<'info>,
+    pub pending_deposit_round_underlying_tokens: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn deposit_with_claim<'info>(
+    accounts: DepositWithClaimAccounts<'_, 'info>,
+    amount: u64,
+    do_transfer: bool,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::DepositWithClaim {
+        payer_authority: accounts.payer_authority,
+        non_payer_authority: accounts.non_payer_authority,
+        sol_transfer_authority: accounts.sol_transfer_authority,
+        authority_check: accounts.authority_check,
+        vault_mint: accounts.vault_mint,
+        volt_vault: accounts.volt_vault,
+        vault_authority: accounts.vault_authority,
+        extra_volt_data: accounts.extra_volt_data,
+        deposit_pool: accounts.deposit_pool,
+        writer_token_pool: accounts.writer_token_pool,
+        vault_token_destination: accounts.vault_token_destination.to_account_info(),
+        underlying_token_source: accounts.underlying_token_source.to_account_info(),
+        round_info: accounts.round_info,
+        round_underlying_tokens: accounts.round_underlying_tokens,
+        pending_deposit_info: accounts.pending_deposit_info,
+        pending_deposit_round_info: accounts.pending_deposit_round_info,
+        pending_deposit_round_volt_tokens: accounts.pending_deposit_round_volt_tokens,
+        pending_deposit_round_underlying_tokens: accounts.pending_deposit_round_underlying_tokens,
+        epoch_info: accounts.epoch_info,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::deposit_with_claim(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+        amount,
+        do_transfer,
+    )?;
+    accounts.vault_token_destination.reload()?;
+    accounts.underlying_token_source.reload()?;
+    Ok(())
+}
+
+pub struct WithdrawWithClaimAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub payer_authority: AccountInfo<'info>,
+    pub non_payer_authority: AccountInfo<'info>,
+    pub authority_check: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub deposit_pool: AccountInfo<'info>,
+    pub underlying_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub vault_token_source: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub round_underlying_tokens: AccountInfo<'info>,
+    pub pending_withdrawal_info: AccountInfo<'info>,
+    pub pending_withdrawal_round_info: AccountInfo<'info>,
+    pub pending_withdrawal_round_underlying_tokens_for_pws: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub fee_acct: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+pub fn withdraw_with_claim<'info>(
+    accounts: WithdrawWithClaimAccounts<'_, 'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::WithdrawWithClaim {
+        payer_authority: accounts.payer_authority,
+        non_payer_authority: accounts.non_payer_authority,
+        authority_check: accounts.authority_check,
+        vault_mint: accounts.vault_mint,
+        volt_vault: accounts.volt_vault,
+        vault_authority: accounts.vault_authority,
+        extra_volt_data: accounts.extra_volt_data,
+        deposit_pool: accounts.deposit_pool,
+        underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        vault_token_source: accounts.vault_token_source.to_account_info(),
+        round_info: accounts.round_info,
+        round_underlying_tokens: accounts.round_underlying_tokens,
+        pending_withdrawal_info: accounts.pending_withdrawal_info,
+        pending_withdrawal_round_info: accounts.pending_withdrawal_round_info,
+        pending_withdrawal_round_underlying_tokens_for_pws: accounts
+            .pending_withdrawal_round_underlying_tokens_for_pws,
+        epoch_info: accounts.epoch_info,
+        fee_acct: accounts.fee_acct,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+        rent: accounts.rent,
+    };
+    crate::cpi::withdraw_with_claim(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+        amount,
+    )?;
+    accounts.underlying_token_destination.reload()?;
+    accounts.vault_token_source.reload()?;
+    Ok(())
+}
+
+pub struct ClaimPendingDepositAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub user_vault_tokens: &'a mut Account<'info, TokenAccount>,
+    pub pending_deposit_round_info: AccountInfo<'info>,
+    pub pending_deposit_round_volt_tokens: AccountInfo<'info>,
+    pub pending_deposit_info: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn claim_pending_deposit<'info>(
+    accounts: ClaimPendingDepositAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::ClaimPendingDeposit {
+        authority: accounts.authority,
+        volt_vault: accounts.volt_vault,
+        extra_volt_data: accounts.extra_volt_data,
+        vault_authority: accounts.vault_authority,
+        user_vault_tokens: accounts.user_vault_tokens.to_account_info(),
+        pending_deposit_round_info: accounts.pending_deposit_round_info,
+        pending_deposit_round_volt_tokens: accounts.pending_deposit_round_volt_tokens,
+        pending_deposit_info: accounts.pending_deposit_info,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::claim_pending_deposit(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+    )?;
+    accounts.user_vault_tokens.reload()?;
+    Ok(())
+}
+
+pub struct ClaimPendingWithdrawalAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub underlying_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub pending_withdrawal_round_info: AccountInfo<'info>,
+    pub pending_withdrawal_info: AccountInfo<'info>,
+    pub round_underlying_tokens_for_pending_withdrawals: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn claim_pending_withdrawal<'info>(
+    accounts: ClaimPendingWithdrawalAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::ClaimPendingWithdrawal {
+        authority: accounts.authority,
+        volt_vault: accounts.volt_vault,
+        extra_volt_data: accounts.extra_volt_data,
+        vault_authority: accounts.vault_authority,
+        vault_mint: accounts.vault_mint,
+        underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        pending_withdrawal_round_info: accounts.pending_withdrawal_round_info,
+        pending_withdrawal_info: accounts.pending_withdrawal_info,
+        round_underlying_tokens_for_pending_withdrawals: accounts
+            .round_underlying_tokens_for_pending_withdrawals,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::claim_pending_withdrawal(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+    )?;
+    accounts.underlying_token_destination.reload()?;
+    Ok(())
+}
+
+pub struct CancelPendingDepositAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub underlying_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub round_underlying_tokens: AccountInfo<'info>,
+    pub pending_deposit_info: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn cancel_pending_deposit<'info>(
+    accounts: CancelPendingDepositAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::CancelPendingDeposit {
+        authority: accounts.authority,
+        volt_vault: accounts.volt_vault,
+        extra_volt_data: accounts.extra_volt_data,
+        vault_authority: accounts.vault_authority,
+        underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        round_info: accounts.round_info,
+        round_underlying_tokens: accounts.round_underlying_tokens,
+        pending_deposit_info: accounts.pending_deposit_info,
+        epoch_info: accounts.epoch_info,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::cancel_pending_deposit(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+    )?;
+    accounts.underlying_token_destination.reload()?;
+    Ok(())
+}
+
+pub struct CancelPendingWithdrawalAccounts<'a, 'info> {
+    pub volt_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub vault_mint: AccountInfo<'info>,
+    pub volt_vault: AccountInfo<'info>,
+    pub extra_volt_data: AccountInfo<'info>,
+    pub vault_authority: AccountInfo<'info>,
+    pub vault_token_destination: &'a mut Account<'info, TokenAccount>,
+    pub round_info: AccountInfo<'info>,
+    pub pending_withdrawal_info: AccountInfo<'info>,
+    pub epoch_info: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+pub fn cancel_pending_withdrawal<'info>(
+    accounts: CancelPendingWithdrawalAccounts<'_, 'info>,
+    signer_seeds: &[&[&[u8]]],
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let cpi_accounts = crate::cpi::accounts::CancelPendingWithdrawal {
+        authority: accounts.authority,
+        vault_mint: accounts.vault_mint,
+        volt_vault: accounts.volt_vault,
+        extra_volt_data: accounts.extra_volt_data,
+        vault_authority: accounts.vault_authority,
+        vault_token_destination: accounts.vault_token_destination.to_account_info(),
+        round_info: accounts.round_info,
+        pending_withdrawal_info: accounts.pending_withdrawal_info,
+        epoch_info: accounts.epoch_info,
+        system_program: accounts.system_program,
+        token_program: accounts.token_program,
+    };
+    crate::cpi::cancel_pending_withdrawal(
+        CpiContext::new_with_signer(accounts.volt_program, cpi_accounts, signer_seeds)
+            .with_remaining_accounts(remaining_accounts.to_vec()),
+    )?;
+    accounts.vault_token_destination.reload()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        account_info, set_test_syscall_stubs, set_token_amount, take_invoked, token_account,
+    };
+    use anchor_lang::InstructionData;
+
+    fn infos(count: usize) -> Vec<AccountInfo<'static>> {
+        (0..count)
+            .map(|_| account_info(Pubkey::new_unique(), Pubkey::default(), vec![], false, true))
+            .collect()
+    }
+
+    #[test]
+    fn withdraw_appends_remaining_accounts_and_reloads() {
+        set_test_syscall_stubs();
+        take_invoked();
+
+        let owner = Pubkey::new_unique();
+        let underlying_info = token_account(Pubkey::new_unique(), Pubkey::new_unique(), owner, 10);
+        let vault_info = token_account(Pubkey::new_unique(), Pubkey::new_unique(), owner, 5);
+        let mut underlying_token_destination =
+            Account::<TokenAccount>::try_from(&underlying_info).unwrap();
+        let mut vault_token_source = Account::<TokenAccount>::try_from(&vault_info).unwrap();
+        // the volt program transfers in between
+        set_token_amount(&underlying_info, 20);
+        set_token_amount(&vault_info, 0);
+
+        let a = infos(18);
+        let remaining = infos(2);
+        withdraw(
+            WithdrawAccounts {
+                volt_program: a[0].clone(),
+                payer_authority: a[1].clone(),
+                non_payer_authority: a[2].clone(),
+                authority_check: a[3].clone(),
+                vault_mint: a[4].clone(),
+                volt_vault: a[5].clone(),
+                extra_volt_data: a[6].clone(),
+                vault_authority: a[7].clone(),
+                whitelist: a[8].clone(),
+                deposit_pool: a[9].clone(),
+                underlying_token_destination: &mut underlying_token_destination,
+                vault_token_source: &mut vault_token_source,
+                round_info: a[10].clone(),
+                round_underlying_tokens: a[11].clone(),
+                pending_withdrawal_info: a[12].clone(),
+                epoch_info: a[13].clone(),
+                fee_acct: a[14].clone(),
+                system_program: a[15].clone(),
+                token_program: a[16].clone(),
+                rent: a[17].clone(),
+            },
+            7,
+            &[&[b"seed"]],
+            &remaining,
+        )
+        .unwrap();
+
+        let invoked = take_invoked();
+        assert_eq!(invoked.len(), 1);
+        let instruction = &invoked[0].instruction;
+        assert_eq!(instruction.program_id, crate::ID);
+        assert_eq!(
+            instruction.data,
+            crate::instruction::Withdraw { amount: 7 }.data()
+        );
+        assert_eq!(invoked[0].signer_seeds, vec![vec![b"seed".to_vec()]]);
+
+        let expected_keys: Vec<Pubkey> = a[1..10]
+            .iter()
+            .map(|info| *info.key)
+            .chain([*underlying_info.key, *vault_info.key])
+            .chain(a[10..18].iter().map(|info| *info.key))
+            .chain(remaining.iter().map(|info| *info.key))
+            .collect();
+        let keys: Vec<Pubkey> = instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(keys, expected_keys);
+
+        assert_eq!(underlying_token_destination.amount, 20);
+        assert_eq!(vault_token_source.amount, 0);
+    }
+
+    #[test]
+    fn claim_pending_deposit_appends_remaining_accounts() {
+        set_test_syscall_stubs();
+        take_invoked();
+
+        let vault_info = token_account(
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            0,
+        );
+        let mut user_vault_tokens = Account::<TokenAccount>::try_from(&vault_info).unwrap();
+        set_token_amount(&vault_info, 3);
+
+        let a = infos(10);
+        let remaining = infos(1);
+        claim_pending_deposit(
+            ClaimPendingDepositAccounts {
+                volt_program: a[0].clone(),
+                authority: a[1].clone(),
+                volt_vault: a[2].clone(),
+                extra_volt_data: a[3].clone(),
+                vault_authority: a[4].clone(),
+                user_vault_tokens: &mut user_vault_tokens,
+                pending_deposit_round_info: a[5].clone(),
+                pending_deposit_round_volt_tokens: a[6].clone(),
+                pending_deposit_info: a[7].clone(),
+                system_program: a[8].clone(),
+                token_program: a[9].clone(),
+            },
+            &[],
+            &remaining,
+        )
+        .unwrap();
+
+        let invoked = take_invoked();
+        assert_eq!(invoked.len(), 1);
+        let instruction = &invoked[0].instruction;
+        assert_eq!(
+            instruction.data,
+            crate::instruction::ClaimPendingDeposit {}.data()
+        );
+        assert!(invoked[0].signer_seeds.is_empty());
+        assert_eq!(instruction.accounts.len(), 11);
+        assert_eq!(instruction.accounts[4].pubkey, *vault_info.key);
+        assert_eq!(instruction.accounts[10].pubkey, *remaining[0].key);
+        assert_eq!(user_vault_tokens.amount, 3);
+    }
+}
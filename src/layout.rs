@@ -0,0 +1,644 @@
+use anchor_lang::prelude::*;
+
+use crate::objects::*;
+
+// Borsh layout of volt accounts, for memcmp filters and non-rust clients
+//  NOTES:
+//  1. offsets are into the account data, i.e. they include the 8 byte anchor discriminator. the comments in objects.rs are end offsets w/o the discriminator
+//  2. every account_layout! below must list all fields of the account in declaration order. missing/extra fields or wrong types don't compile, order is checked by the tests against the borsh serialization
+//  3. LEN (the space allocated w/o discriminator) is asserted at compile time to be >= the serialized size
+//  4. PrincipalProtectionVaultV1 isn't listed, its fields after vault_name (a String) have no fixed offset
+//  5. UlOpenOrdersMetadata isn't listed, its only field (initialized: bool, at offset 8) is private to objects.rs
+
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldLayout {
+    pub name: &'static str,
+    pub ty: &'static str,
+    // offset into the account data (incl. discriminator)
+    pub offset: usize,
+    // serialized size. for variable length fields (Vec/String), only the 4 byte length prefix
+    pub size: usize,
+    pub is_variable: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AccountLayout {
+    pub name: &'static str,
+    pub fields: &'static [FieldLayout],
+    // serialized size w/o discriminator. a minimum if the last field is variable length
+    pub size: usize,
+    // LEN of the account, space allocated is DISCRIMINATOR_LEN + len
+    pub len: usize,
+}
+
+impl AccountLayout {
+    pub fn field(&self, name: &str) -> Option<&FieldLayout> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn offset_of(&self, name: &str) -> Option<usize> {
+        self.field(name).map(|field| field.offset)
+    }
+
+    pub fn has_variable_tail(&self) -> bool {
+        self.fields.last().is_some_and(|field| field.is_variable)
+    }
+}
+
+// implemented for every account listed below, same as the inherent LAYOUT const
+pub trait HasLayout {
+    const LAYOUT: AccountLayout;
+}
+
+// serialized (borsh) size of a field type
+pub trait FieldSize {
+    const SIZE: usize;
+    const IS_VARIABLE: bool = false;
+}
+
+macro_rules! field_size {
+    ($($ty:ty => $size:expr),* $(,)?) => {
+        $(
+            impl FieldSize for $ty {
+                const SIZE: usize = $size;
+            }
+        )*
+    };
+}
+
+field_size!(
+    bool => 1,
+    u8 => 1,
+    u16 => 2,
+    u64 => 8,
+    i64 => 8,
+    f64 => 8,
+    Pubkey => 32,
+);
+
+impl FieldSize for String {
+    const SIZE: usize = 4;
+    const IS_VARIABLE: bool = true;
+}
+
+impl<T> FieldSize for Vec<T> {
+    const SIZE: usize = 4;
+    const IS_VARIABLE: bool = true;
+}
+
+const fn field_layouts<const N: usize>(
+    names: [&'static str; N],
+    tys: [&'static str; N],
+    sizes: [usize; N],
+    is_variable: [bool; N],
+) -> [FieldLayout; N] {
+    let mut fields = [FieldLayout {
+        name: "",
+        ty: "",
+        offset: 0,
+        size: 0,
+        is_variable: false,
+    }; N];
+    let mut offset = DISCRIMINATOR_LEN;
+    let mut i = 0;
+    while i < N {
+        assert!(
+            !is_variable[i] || i + 1 == N,
+            "only the last field may be variable length"
+        );
+        fields[i] = FieldLayout {
+            name: names[i],
+            ty: tys[i],
+            offset,
+            size: sizes[i],
+            is_variable: is_variable[i],
+        };
+        offset += sizes[i];
+        i += 1;
+    }
+    fields
+}
+
+const fn serialized_size(fields: &[FieldLayout]) -> usize {
+    match fields.last() {
+        Some(field) => field.offset + field.size - DISCRIMINATOR_LEN,
+        None => 0,
+    }
+}
+
+macro_rules! account_layout {
+    ($account:ident $(($len:expr))? { $( $field:ident: $ty:ty, )* }) => {
+        impl $account {
+            pub const LAYOUT: AccountLayout = {
+                const FIELDS: &[FieldLayout] = &field_layouts(
+                    [$(stringify!($field)),*],
+                    [$(stringify!($ty)),*],
+                    [$(<$ty as FieldSize>::SIZE),*],
+                    [$(<$ty as FieldSize>::IS_VARIABLE),*],
+                );
+                AccountLayout {
+                    name: stringify!($account),
+                    fields: FIELDS,
+                    size: serialized_size(FIELDS),
+                    len: account_layout!(@len $account $(, $len)?),
+                }
+            };
+        }
+
+        impl HasLayout for $account {
+            const LAYOUT: AccountLayout = $account::LAYOUT;
+        }
+
+        const _: () = assert!(
+            $account::LAYOUT.size <= $account::LAYOUT.len,
+            concat!(stringify!($account), " LEN is smaller than its serialized size")
+        );
+
+        // doesn't compile unless every field is listed with its type
+        const _: fn(&$account) = |account| {
+            let $account { $($field),* } = account;
+            $( let _: &$ty = $field; )*
+        };
+
+        #[cfg(test)]
+        impl tests::SampleAccount for $account {
+            fn sample() -> Self {
+                let mut seed = 0u8;
+                $account {
+                    $($field: {
+                        seed += 1;
+                        <$ty as tests::Sample>::sample(seed)
+                    },)*
+                }
+            }
+
+            fn serialized_fields(&self) -> Vec<(&'static str, Vec<u8>)> {
+                vec![$((stringify!($field), self.$field.try_to_vec().unwrap())),*]
+            }
+        }
+    };
+    (@len $account:ident) => {
+        $account::LEN
+    };
+    (@len $account:ident, $len:expr) => {
+        $len
+    };
+}
+
+account_layout!(VoltVault {
+    admin_key: Pubkey,
+    seed: Pubkey,
+    transfer_window: u64,
+    start_transfer_time: u64,
+    end_transfer_time: u64,
+    initialized: bool,
+    curr_option_was_settled: bool,
+    must_swap_premium_to_underlying: bool,
+    next_option_was_set: bool,
+    first_ever_option_was_set: bool,
+    instant_transfers_enabled: bool,
+    prepare_is_finished: bool,
+    enter_is_finished: bool,
+    round_has_started: bool,
+    round_number: u64,
+    total_underlying_pre_enter: u64,
+    total_underlying_post_settle: u64,
+    total_volt_tokens_post_settle: u64,
+    vault_authority: Pubkey,
+    deposit_pool: Pubkey,
+    premium_pool: Pubkey,
+    option_pool: Pubkey,
+    writer_token_pool: Pubkey,
+    vault_mint: Pubkey,
+    underlying_asset_mint: Pubkey,
+    quote_asset_mint: Pubkey,
+    option_mint: Pubkey,
+    writer_token_mint: Pubkey,
+    option_market: Pubkey,
+    vault_type: u64,
+    underlying_amount_per_contract: u64,
+    quote_amount_per_contract: u64,
+    expiration_unix_timestamp: i64,
+    expiration_interval: u64,
+    upper_bound_otm_strike_factor: u64,
+    have_taken_withdrawal_fees: bool,
+    serum_spot_market: Pubkey,
+    open_orders_bump: u8,
+    open_orders_init_bump: u8,
+    ul_open_orders_bump: u8,
+    ul_open_orders: Pubkey,
+    ul_open_orders_initialized: bool,
+    bump_authority: u8,
+    serum_order_size_options: u64,
+    individual_capacity: u64,
+    serum_order_type: u64,
+    serum_limit: u16,
+    serum_self_trade_behavior: u16,
+    serum_client_order_id: u64,
+    whitelist_token_mint: Pubkey,
+    permissioned_market_premium_mint: Pubkey,
+    permissioned_market_premium_pool: Pubkey,
+    capacity: u64,
+});
+
+account_layout!(ExtraVoltData {
+    is_whitelisted: bool,
+    whitelist: Pubkey,
+    is_for_dao: bool,
+    dao_program_id: Pubkey,
+    deposit_mint: Pubkey,
+    target_leverage: f64,
+    target_leverage_lenience: f64,
+    exit_early_ratio: f64,
+    entropy_program_id: Pubkey,
+    entropy_group: Pubkey,
+    entropy_account: Pubkey,
+    power_perp_market: Pubkey,
+    have_resolved_deposits: bool,
+    done_rebalancing: bool,
+    non_payer_authority: Pubkey,
+    serum_program_id: Pubkey,
+    entropy_cache: Pubkey,
+    spot_perp_market: Pubkey,
+    extra_key_7: Pubkey,
+    extra_key_8: Pubkey,
+    extra_key_9: Pubkey,
+    extra_key_10: Pubkey,
+    extra_key_11: Pubkey,
+    extra_key_12: Pubkey,
+    extra_key_13: Pubkey,
+    extra_key_14: Pubkey,
+    net_withdrawals: u64,
+    max_quote_pos_change: u64,
+    target_hedge_lenience: f64,
+    unused_uint_four: u64,
+    unused_uint_five: u64,
+    unused_uint_six: u64,
+    unused_uint_7: u64,
+    unused_uint_8: u64,
+    unused_uint_9: u64,
+    unused_uint_10: u64,
+    unused_uint_11: u64,
+    unused_uint_12: u64,
+    turn_off_deposits_and_withdrawals: bool,
+    rebalance_is_ready: bool,
+    unused_bool1234: bool,
+    done_rebalancing_power_perp: bool,
+    is_hedging_on: bool,
+    have_taken_performance_fees: bool,
+});
+
+account_layout!(Round {
+    number: u64,
+    underlying_from_pending_deposits: u64,
+    volt_tokens_from_pending_withdrawals: u64,
+    underlying_pre_enter: u64,
+    underlying_post_settle: u64,
+    premium_farmed: u64,
+});
+
+account_layout!(FriktionEpochInfo {
+    vault_token_price: f64,
+    pct_pnl: f64,
+    number: u64,
+    underlying_pre_enter: u64,
+    underlying_post_settle: u64,
+    volt_token_supply: u64,
+    pnl: i64,
+    performance_fees: u64,
+    withdrawal_fees: u64,
+    pending_deposits: u64,
+    pending_withdrawals_volt_tokens: u64,
+    pending_withdrawals: u64,
+    canceled_withdrawals: u64,
+    canceled_deposits: u64,
+    total_withdrawals: u64,
+    total_deposits: u64,
+    instant_deposits: u64,
+    instant_withdrawals: u64,
+    unused_uint_1: u64,
+    minted_options: u64,
+    enter_num_times_called: u64,
+    swap_premium_num_times_called: u64,
+    option_key: Pubkey,
+    extra_key_four: Pubkey,
+    extra_key_5: Pubkey,
+    extra_key_6: Pubkey,
+    extra_key_7: Pubkey,
+    extra_key_8: Pubkey,
+    extra_key_9: Pubkey,
+    extra_key_10: Pubkey,
+    extra_key_11: Pubkey,
+    extra_key_12: Pubkey,
+    unused_uint_four: u64,
+    unused_uint_five: u64,
+    unused_uint_six: u64,
+    unused_uint_7: u64,
+    unused_uint_8: u64,
+    unused_uint_9: u64,
+    unused_uint_10: u64,
+    unused_uint_11: u64,
+    unused_uint_12: u64,
+    unused_bool_one: bool,
+    unused_bool_two: bool,
+    unused_bool_three: bool,
+    unused_bool_four: bool,
+    unused_bool_five: bool,
+    unused_bool_six: bool,
+});
+
+account_layout!(EntropyRound {
+    instant_deposits_native: u64,
+    prev_entropy_account_deposits: u64,
+    initial_equity: f64,
+    new_equity_post_deposit: f64,
+    deposit_amt: f64,
+    withdraw_comp_from_deposit: u64,
+    net_deposits: f64,
+    deposit_amt_native: u64,
+    withdraw_amt_native: u64,
+    total_volt_supply: u64,
+    oracle_price: f64,
+    acct_equity_start: f64,
+    acct_equity_before_next_rebalance: f64,
+    pnl_quote: f64,
+    performance_fees_quote: f64,
+    temp1: Pubkey,
+    temp2: Pubkey,
+    temp3: Pubkey,
+    extra_key_11: Pubkey,
+    extra_key_12: Pubkey,
+    unused_uint_four: u64,
+    unused_uint_five: u64,
+    unused_uint_six: u64,
+    unused_uint_12: u64,
+    unused_float1: f64,
+    unused_float2: f64,
+    unused_float3: f64,
+    unused_float4: f64,
+    unused_bool_one: bool,
+    unused_bool_two: bool,
+    unused_bool_three: bool,
+    unused_bool_four: bool,
+});
+
+account_layout!(EntropyMetadata {
+    target_hedge_ratio: f64,
+    rebalancing_lenience: f64,
+    required_basis_from_oracle: f64,
+    extra_key_3: Pubkey,
+    extra_key_4: Pubkey,
+    extra_key_5: Pubkey,
+    extra_key_6: Pubkey,
+    extra_key_7: Pubkey,
+    extra_key_8: Pubkey,
+    extra_key_9: Pubkey,
+    extra_key_10: Pubkey,
+    extra_key_11: Pubkey,
+    extra_key_12: Pubkey,
+    unused_uint_four: u64,
+    unused_uint_five: u64,
+    unused_uint_six: u64,
+    unused_uint_12: u64,
+    unused_uint_123: u64,
+    unused_uint_456: u64,
+    unused_uint_789: u64,
+    unused_uint_102: u64,
+    unused_float1: f64,
+    unused_float2: f64,
+    unused_float3: f64,
+    unused_float4: f64,
+    unused_float5: f64,
+    unused_float6: f64,
+    unused_float7: f64,
+    unused_float8: f64,
+    unused_float9: f64,
+    unused_float10: f64,
+    unused_float11: f64,
+    unused_float12: f64,
+    unused_bool_one: bool,
+    unused_bool_two: bool,
+    unused_bool_three: bool,
+    unused_bool_four: bool,
+    unused_bool_five: bool,
+    unused_bool_six: bool,
+    unused_bool_seven: bool,
+    unused_bool_eight: bool,
+    unused_bool_nine: bool,
+    unused_bool_ten: bool,
+    vault_name: String,
+});
+
+account_layout!(PendingDeposit {
+    initialized: bool,
+    round_number: u64,
+    num_underlying_deposited: u64,
+});
+
+account_layout!(PendingWithdrawal {
+    initialized: bool,
+    round_number: u64,
+    num_volt_redeemed: u64,
+});
+
+// len excludes the addresses
+account_layout!(Whitelist (Whitelist::BASE_LEN) {
+    admin: Pubkey,
+    addresses: Vec<Pubkey>,
+});
+
+pub const ACCOUNT_LAYOUTS: &[AccountLayout] = &[
+    VoltVault::LAYOUT,
+    ExtraVoltData::LAYOUT,
+    Round::LAYOUT,
+    FriktionEpochInfo::LAYOUT,
+    EntropyRound::LAYOUT,
+    EntropyMetadata::LAYOUT,
+    PendingDeposit::LAYOUT,
+    PendingWithdrawal::LAYOUT,
+    Whitelist::LAYOUT,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // field value derived from seed, distinct for every field of an account (bools alternate)
+    pub trait Sample {
+        fn sample(seed: u8) -> Self;
+    }
+
+    impl Sample for bool {
+        fn sample(seed: u8) -> Self {
+            seed % 2 == 1
+        }
+    }
+
+    impl Sample for u8 {
+        fn sample(seed: u8) -> Self {
+            seed
+        }
+    }
+
+    impl Sample for u16 {
+        fn sample(seed: u8) -> Self {
+            u16::from_le_bytes([seed; 2])
+        }
+    }
+
+    impl Sample for u64 {
+        fn sample(seed: u8) -> Self {
+            u64::from_le_bytes([seed; 8])
+        }
+    }
+
+    impl Sample for i64 {
+        fn sample(seed: u8) -> Self {
+            i64::from_le_bytes([seed; 8])
+        }
+    }
+
+    impl Sample for f64 {
+        fn sample(seed: u8) -> Self {
+            f64::from_bits(u64::sample(seed))
+        }
+    }
+
+    impl Sample for Pubkey {
+        fn sample(seed: u8) -> Self {
+            Pubkey::new_from_array([seed; 32])
+        }
+    }
+
+    impl Sample for String {
+        fn sample(seed: u8) -> Self {
+            format!("volt {}", seed)
+        }
+    }
+
+    impl<T: Sample> Sample for Vec<T> {
+        fn sample(seed: u8) -> Self {
+            vec![T::sample(seed), T::sample(seed.wrapping_add(100))]
+        }
+    }
+
+    pub trait SampleAccount: Sized {
+        fn sample() -> Self;
+
+        // (name, borsh serialization) of every field, in the order listed in account_layout!
+        fn serialized_fields(&self) -> Vec<(&'static str, Vec<u8>)>;
+    }
+
+    fn assert_layout<
+        T: HasLayout + SampleAccount + AccountSerialize + anchor_lang::Discriminator,
+    >() {
+        let layout = T::LAYOUT;
+        let account = T::sample();
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        assert_eq!(data[..DISCRIMINATOR_LEN], T::discriminator());
+
+        let serialized_fields = account.serialized_fields();
+        assert_eq!(layout.fields.len(), serialized_fields.len());
+        for (field, (name, bytes)) in layout.fields.iter().zip(serialized_fields) {
+            assert_eq!(field.name, name);
+            if field.is_variable {
+                assert_eq!(field.size, 4, "{}.{}", layout.name, name);
+                assert_eq!(data[field.offset..], bytes[..], "{}.{}", layout.name, name);
+            } else {
+                assert_eq!(field.size, bytes.len(), "{}.{}", layout.name, name);
+                assert_eq!(
+                    data[field.offset..field.offset + field.size],
+                    bytes[..],
+                    "{}.{}",
+                    layout.name,
+                    name
+                );
+            }
+        }
+
+        if !layout.has_variable_tail() {
+            assert_eq!(data.len(), DISCRIMINATOR_LEN + layout.size);
+        }
+    }
+
+    #[test]
+    fn volt_vault_layout() {
+        assert_layout::<VoltVault>();
+    }
+
+    #[test]
+    fn extra_volt_data_layout() {
+        assert_layout::<ExtraVoltData>();
+    }
+
+    #[test]
+    fn round_layout() {
+        assert_layout::<Round>();
+    }
+
+    #[test]
+    fn friktion_epoch_info_layout() {
+        assert_layout::<FriktionEpochInfo>();
+    }
+
+    #[test]
+    fn entropy_round_layout() {
+        assert_layout::<EntropyRound>();
+    }
+
+    #[test]
+    fn entropy_metadata_layout() {
+        assert_layout::<EntropyMetadata>();
+    }
+
+    #[test]
+    fn pending_deposit_layout() {
+        assert_layout::<PendingDeposit>();
+    }
+
+    #[test]
+    fn pending_withdrawal_layout() {
+        assert_layout::<PendingWithdrawal>();
+    }
+
+    #[test]
+    fn whitelist_layout() {
+        assert_layout::<Whitelist>();
+    }
+
+    #[test]
+    fn sizes_match_len() {
+        // fixed size accounts fill their LEN exactly
+        for layout in ACCOUNT_LAYOUTS {
+            if !layout.has_variable_tail() {
+                assert_eq!(layout.size, layout.len, "{}", layout.name);
+            }
+        }
+        // 24 bytes left for vault_name
+        assert!(EntropyMetadata::LAYOUT.has_variable_tail());
+        assert_eq!(
+            EntropyMetadata::LAYOUT.len - EntropyMetadata::LAYOUT.size,
+            24
+        );
+        assert!(Whitelist::LAYOUT.has_variable_tail());
+    }
+
+    #[test]
+    fn known_offsets() {
+        assert_eq!(VoltVault::LAYOUT.offset_of("round_number"), Some(8 + 97));
+        assert_eq!(VoltVault::LAYOUT.offset_of("vault_mint"), Some(8 + 289));
+        assert_eq!(
+            ExtraVoltData::LAYOUT.offset_of("deposit_mint"),
+            Some(8 + 66)
+        );
+        assert_eq!(
+            PendingDeposit::LAYOUT.offset_of("round_number"),
+            Some(8 + 1)
+        );
+        assert_eq!(Round::LAYOUT.offset_of("number"), Some(8));
+        assert_eq!(Round::LAYOUT.offset_of("missing"), None);
+    }
+}
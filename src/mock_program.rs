@@ -0,0 +1,1518 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::error::ErrorCode;
+use crate::pda::{
+    EPOCH_INFO_SEED, EXTRA_VOLT_DATA_SEED, ROUND_INFO_SEED, ROUND_UL_PENDING_SEED,
+    ROUND_UNDERLYING_TOKENS_SEED, ROUND_VOLT_TOKENS_SEED,
+};
+use crate::preflight::{
+    check, check_cancel_pending_deposit, check_cancel_pending_withdrawal,
+    check_claim_pending_deposit, check_claim_pending_withdrawal, check_phase,
+};
+use crate::share_price::{checked_add, checked_sub, preview_instant_deposit, SharePrice};
+use crate::volt_instruction::volt_instruction_sighash;
+use crate::{
+    CancelPendingDeposit, CancelPendingWithdrawal, ClaimPendingDeposit, ClaimPendingWithdrawal,
+    Deposit, DepositWithClaim, ExtraVoltData, FriktionEpochInfo, InstructionKind, PendingDeposit,
+    PendingWithdrawal, PreflightVolt, Round, VoltVault, Whitelist, Withdraw, WithdrawWithClaim,
+};
+
+// Handlers of the user instructions and a minimal round roll (mock_start_round/mock_end_round), for loading this crate into
+// solana-program-test (processor!(volt_abi::mock_program::entry)) with the "mock-program" feature.
+//  NOTES:
+//  1. validation goes through preflight.rs, so handlers fail with the errors the volt program returns
+//  2. the mock signs as vault_authority with MOCK_VAULT_AUTHORITY_SEED and volt_vault.bump_authority, test fixtures must set
+//     volt_vault.vault_authority to find_mock_vault_authority_address and make it the authority of the vault mint and volt token accounts
+//  3. instant transfers (volt_vault.instant_transfers_enabled) mint/redeem at the live price, otherwise deposits/withdrawals are pending until mock_end_round
+//  4. mock_start_round and mock_end_round skip the rebalance pipeline (no options are traded), so mock_end_round settles at the price of the deposit pool
+//  5. withdrawal and performance fees, sol transfers (do_transfer) and volt type specific remaining accounts are not mocked
+
+// the mock's vault_authority PDA is [volt, MOCK_VAULT_AUTHORITY_SEED]
+pub const MOCK_VAULT_AUTHORITY_SEED: &[u8] = b"vaultAuthority";
+
+pub fn find_mock_vault_authority_address(volt: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[volt.as_ref(), MOCK_VAULT_AUTHORITY_SEED], &crate::id())
+}
+
+///////// helpers ///////////
+
+// seeds of vault_authority, checked against volt_vault.vault_authority
+struct VaultSigner {
+    volt_key: Pubkey,
+    bump: [u8; 1],
+}
+
+impl VaultSigner {
+    fn new(volt_key: Pubkey, volt_vault: &VoltVault) -> Result<Self> {
+        let bump = [volt_vault.bump_authority];
+        let vault_authority = Pubkey::create_program_address(
+            &[volt_key.as_ref(), MOCK_VAULT_AUTHORITY_SEED, &bump],
+            &crate::id(),
+        )
+        .map_err(|_| error!(ErrorCode::VaultAuthorityDoesNotMatch))?;
+        require_keys_eq!(
+            vault_authority,
+            volt_vault.vault_authority,
+            ErrorCode::VaultAuthorityDoesNotMatch
+        );
+        Ok(VaultSigner { volt_key, bump })
+    }
+
+    fn seeds(&self) -> [&[u8]; 3] {
+        [
+            self.volt_key.as_ref(),
+            MOCK_VAULT_AUTHORITY_SEED,
+            &self.bump,
+        ]
+    }
+}
+
+fn transfer_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from,
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+fn mint_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint,
+                to,
+                authority,
+            },
+            signer_seeds,
+        ),
+        amount,
+    )
+}
+
+fn burn_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    token::burn(
+        CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint,
+                from,
+                authority,
+            },
+        ),
+        amount,
+    )
+}
+
+// authority_check must be one of the two authorities, pending deposit/withdrawal PDAs are derived from it
+fn check_authority_check(
+    authority_check: &AccountInfo,
+    payer_authority: &AccountInfo,
+    non_payer_authority: &AccountInfo,
+) -> Result<()> {
+    if authority_check.key() != payer_authority.key()
+        && authority_check.key() != non_payer_authority.key()
+    {
+        return err!(ErrorCode::InvalidAuthorityCheck);
+    }
+    Ok(())
+}
+
+// user token accounts may be owned by either payer_authority or non_payer_authority
+fn user_token_authority<'info>(
+    user_tokens: &TokenAccount,
+    payer_authority: &AccountInfo<'info>,
+    non_payer_authority: &AccountInfo<'info>,
+) -> AccountInfo<'info> {
+    if user_tokens.owner == payer_authority.key() {
+        payer_authority.clone()
+    } else {
+        non_payer_authority.clone()
+    }
+}
+
+// whitelist is only loaded for whitelisted volts, from whichever of accounts is extra_volt_data.whitelist
+fn load_whitelist<'info>(
+    extra_volt_data: &ExtraVoltData,
+    accounts: &[AccountInfo<'info>],
+) -> Result<Option<Account<'info, Whitelist>>> {
+    if !extra_volt_data.is_whitelisted {
+        return Ok(None);
+    }
+    accounts
+        .iter()
+        .find(|account| account.key() == extra_volt_data.whitelist)
+        .map(Account::try_from)
+        .transpose()
+}
+
+fn check_admin(authority: &Pubkey, volt_vault: &VoltVault) -> Result<()> {
+    require_keys_eq!(
+        *authority,
+        volt_vault.admin_key,
+        ErrorCode::InvalidAuthorityForPermissionedInstruction
+    );
+    Ok(())
+}
+
+// accounts deposit and deposit_with_claim move underlying (and mint volt tokens) with
+struct DepositTransfer<'a, 'info> {
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    vault_mint: &'a Account<'info, Mint>,
+    deposit_pool: &'a Account<'info, TokenAccount>,
+    user_authority: AccountInfo<'info>,
+    user_vault_tokens: AccountInfo<'info>,
+    user_ul_tokens: AccountInfo<'info>,
+    round_underlying_tokens: AccountInfo<'info>,
+}
+
+// minted immediately if instant transfers are enabled, otherwise added to the pending deposit of the current round
+fn deposit_underlying(
+    volt_vault: &VoltVault,
+    vault_signer: &VaultSigner,
+    transfer: DepositTransfer,
+    round: &mut Round,
+    pending_deposit: &mut PendingDeposit,
+    epoch_info: &mut FriktionEpochInfo,
+    deposit_amount: u64,
+) -> Result<()> {
+    if volt_vault.instant_transfers_enabled {
+        let volt_tokens = preview_instant_deposit(
+            transfer.vault_mint.supply,
+            transfer.deposit_pool.amount,
+            deposit_amount,
+        )?;
+        transfer_tokens(
+            &transfer.token_program,
+            transfer.user_ul_tokens,
+            transfer.deposit_pool.to_account_info(),
+            transfer.user_authority,
+            &[],
+            deposit_amount,
+        )?;
+        let seeds = vault_signer.seeds();
+        mint_tokens(
+            &transfer.token_program,
+            transfer.vault_mint.to_account_info(),
+            transfer.user_vault_tokens,
+            transfer.vault_authority,
+            &[&seeds[..]],
+            volt_tokens,
+        )?;
+        epoch_info.instant_deposits = checked_add(epoch_info.instant_deposits, deposit_amount)?;
+    } else {
+        transfer_tokens(
+            &transfer.token_program,
+            transfer.user_ul_tokens,
+            transfer.round_underlying_tokens,
+            transfer.user_authority,
+            &[],
+            deposit_amount,
+        )?;
+        if pending_deposit.round_number != volt_vault.round_number {
+            pending_deposit.round_number = volt_vault.round_number;
+            pending_deposit.num_underlying_deposited = 0;
+        }
+        pending_deposit.initialized = true;
+        pending_deposit.num_underlying_deposited =
+            checked_add(pending_deposit.num_underlying_deposited, deposit_amount)?;
+        round.underlying_from_pending_deposits =
+            checked_add(round.underlying_from_pending_deposits, deposit_amount)?;
+        epoch_info.pending_deposits = checked_add(epoch_info.pending_deposits, deposit_amount)?;
+    }
+    epoch_info.total_deposits = checked_add(epoch_info.total_deposits, deposit_amount)?;
+    Ok(())
+}
+
+// accounts withdraw and withdraw_with_claim burn volt tokens (and move underlying) with
+struct WithdrawTransfer<'a, 'info> {
+    token_program: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    vault_mint: &'a Account<'info, Mint>,
+    deposit_pool: &'a Account<'info, TokenAccount>,
+    user_authority: AccountInfo<'info>,
+    vault_token_source: AccountInfo<'info>,
+    underlying_token_destination: AccountInfo<'info>,
+}
+
+// redeemed immediately if instant transfers are enabled, otherwise added to the pending withdrawal of the current round.
+// volt tokens are burned right away in both cases
+fn withdraw_volt_tokens(
+    volt_vault: &VoltVault,
+    vault_signer: &VaultSigner,
+    transfer: WithdrawTransfer,
+    round: &mut Round,
+    pending_withdrawal: &mut PendingWithdrawal,
+    epoch_info: &mut FriktionEpochInfo,
+    withdraw_amount: u64,
+) -> Result<()> {
+    if volt_vault.instant_transfers_enabled {
+        let underlying =
+            SharePrice::from_balances(transfer.vault_mint.supply, transfer.deposit_pool.amount)
+                .underlying_for_volt_tokens(withdraw_amount)?;
+        burn_tokens(
+            &transfer.token_program,
+            transfer.vault_mint.to_account_info(),
+            transfer.vault_token_source,
+            transfer.user_authority,
+            withdraw_amount,
+        )?;
+        let seeds = vault_signer.seeds();
+        transfer_tokens(
+            &transfer.token_program,
+            transfer.deposit_pool.to_account_info(),
+            transfer.underlying_token_destination,
+            transfer.vault_authority,
+            &[&seeds[..]],
+            underlying,
+        )?;
+        epoch_info.instant_withdrawals = checked_add(epoch_info.instant_withdrawals, underlying)?;
+        epoch_info.total_withdrawals = checked_add(epoch_info.total_withdrawals, underlying)?;
+    } else {
+        burn_tokens(
+            &transfer.token_program,
+            transfer.vault_mint.to_account_info(),
+            transfer.vault_token_source,
+            transfer.user_authority,
+            withdraw_amount,
+        )?;
+        if pending_withdrawal.round_number != volt_vault.round_number {
+            pending_withdrawal.round_number = volt_vault.round_number;
+            pending_withdrawal.num_volt_redeemed = 0;
+        }
+        pending_withdrawal.initialized = true;
+        pending_withdrawal.num_volt_redeemed =
+            checked_add(pending_withdrawal.num_volt_redeemed, withdraw_amount)?;
+        round.volt_tokens_from_pending_withdrawals =
+            checked_add(round.volt_tokens_from_pending_withdrawals, withdraw_amount)?;
+        epoch_info.pending_withdrawals_volt_tokens =
+            checked_add(epoch_info.pending_withdrawals_volt_tokens, withdraw_amount)?;
+    }
+    Ok(())
+}
+
+// transfers the round's volt tokens owed to a pending deposit from an older round, and clears it
+fn claim_volt_tokens<'info>(
+    vault_signer: &VaultSigner,
+    token_program: &AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    round: &mut Round,
+    round_volt_tokens: &Account<'info, TokenAccount>,
+    pending_deposit: &mut PendingDeposit,
+    user_vault_tokens: AccountInfo<'info>,
+) -> Result<()> {
+    let volt_tokens = pending_deposit.claimable_volt_tokens(round, round_volt_tokens.amount)?;
+    let seeds = vault_signer.seeds();
+    transfer_tokens(
+        token_program,
+        round_volt_tokens.to_account_info(),
+        user_vault_tokens,
+        vault_authority,
+        &[&seeds[..]],
+        volt_tokens,
+    )?;
+    round.underlying_from_pending_deposits = checked_sub(
+        round.underlying_from_pending_deposits,
+        pending_deposit.num_underlying_deposited,
+    )?;
+    pending_deposit.round_number = 0;
+    pending_deposit.num_underlying_deposited = 0;
+    Ok(())
+}
+
+// transfers the round's underlying owed to a pending withdrawal from an older round, and clears it
+fn claim_underlying<'info>(
+    vault_signer: &VaultSigner,
+    token_program: &AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    round: &mut Round,
+    round_ul_pending: &Account<'info, TokenAccount>,
+    pending_withdrawal: &mut PendingWithdrawal,
+    underlying_token_destination: AccountInfo<'info>,
+) -> Result<()> {
+    let underlying = pending_withdrawal.claimable_underlying(round, round_ul_pending.amount)?;
+    let seeds = vault_signer.seeds();
+    transfer_tokens(
+        token_program,
+        round_ul_pending.to_account_info(),
+        underlying_token_destination,
+        vault_authority,
+        &[&seeds[..]],
+        underlying,
+    )?;
+    round.volt_tokens_from_pending_withdrawals = checked_sub(
+        round.volt_tokens_from_pending_withdrawals,
+        pending_withdrawal.num_volt_redeemed,
+    )?;
+    pending_withdrawal.round_number = 0;
+    pending_withdrawal.num_volt_redeemed = 0;
+    Ok(())
+}
+
+///////// user instructions ///////////
+
+pub(crate) fn deposit(ctx: Context<Deposit>, deposit_amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    check_authority_check(
+        &accounts.authority_check,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    )?;
+    let whitelist = load_whitelist(
+        &accounts.extra_volt_data,
+        std::slice::from_ref(&accounts.whitelist),
+    )?;
+    check(
+        PreflightVolt {
+            volt_vault: &accounts.volt_vault,
+            extra_volt_data: &accounts.extra_volt_data,
+            round: &accounts.round_info,
+            deposit_pool: &accounts.deposit_pool,
+            whitelist: whitelist.as_deref(),
+        }
+        .deposit(
+            &accounts.authority_check.key(),
+            Some(&**accounts.pending_deposit_info),
+            &accounts.user_vault_tokens,
+            &accounts.user_ul_tokens,
+            deposit_amount,
+            Clock::get()?.unix_timestamp,
+        ),
+    )?;
+
+    let volt_vault = **accounts.volt_vault;
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &volt_vault)?;
+    let user_authority = user_token_authority(
+        &accounts.user_ul_tokens,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    );
+    deposit_underlying(
+        &volt_vault,
+        &vault_signer,
+        DepositTransfer {
+            token_program: accounts.token_program.to_account_info(),
+            vault_authority: accounts.vault_authority.clone(),
+            vault_mint: &accounts.vault_mint,
+            deposit_pool: &accounts.deposit_pool,
+            user_authority,
+            user_vault_tokens: accounts.user_vault_tokens.to_account_info(),
+            user_ul_tokens: accounts.user_ul_tokens.to_account_info(),
+            round_underlying_tokens: accounts.round_underlying_tokens.to_account_info(),
+        },
+        &mut accounts.round_info,
+        &mut accounts.pending_deposit_info,
+        &mut accounts.epoch_info,
+        deposit_amount,
+    )
+}
+
+pub(crate) fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+    let accounts = ctx.accounts;
+    check_authority_check(
+        &accounts.authority_check,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    )?;
+    let whitelist = load_whitelist(
+        &accounts.extra_volt_data,
+        std::slice::from_ref(&accounts.whitelist),
+    )?;
+    check(
+        PreflightVolt {
+            volt_vault: &accounts.volt_vault,
+            extra_volt_data: &accounts.extra_volt_data,
+            round: &accounts.round_info,
+            deposit_pool: &accounts.deposit_pool,
+            whitelist: whitelist.as_deref(),
+        }
+        .withdraw(
+            &accounts.authority_check.key(),
+            Some(&**accounts.pending_withdrawal_info),
+            &accounts.vault_token_source,
+            &accounts.underlying_token_destination,
+            amount,
+            Clock::get()?.unix_timestamp,
+        ),
+    )?;
+
+    let volt_vault = **accounts.volt_vault;
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &volt_vault)?;
+    let user_authority = user_token_authority(
+        &accounts.vault_token_source,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    );
+    withdraw_volt_tokens(
+        &volt_vault,
+        &vault_signer,
+        WithdrawTransfer {
+            token_program: accounts.token_program.to_account_info(),
+            vault_authority: accounts.vault_authority.clone(),
+            vault_mint: &accounts.vault_mint,
+            deposit_pool: &accounts.deposit_pool,
+            user_authority,
+            vault_token_source: accounts.vault_token_source.to_account_info(),
+            underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        },
+        &mut accounts.round_info,
+        &mut accounts.pending_withdrawal_info,
+        &mut accounts.epoch_info,
+        amount,
+    )
+}
+
+// whitelisted volts must pass the whitelist as a remaining account, DepositWithClaim has no whitelist field
+pub(crate) fn deposit_with_claim(
+    ctx: Context<DepositWithClaim>,
+    amount: u64,
+    _do_transfer: bool,
+) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    let accounts = ctx.accounts;
+    check_authority_check(
+        &accounts.authority_check,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    )?;
+    let whitelist = load_whitelist(&accounts.extra_volt_data, remaining_accounts)?;
+    check(
+        PreflightVolt {
+            volt_vault: &accounts.volt_vault,
+            extra_volt_data: &accounts.extra_volt_data,
+            round: &accounts.round_info,
+            deposit_pool: &accounts.deposit_pool,
+            whitelist: whitelist.as_deref(),
+        }
+        .deposit_with_claim(
+            &accounts.authority_check.key(),
+            Some(&**accounts.pending_deposit_info),
+            &accounts.vault_token_destination,
+            &accounts.underlying_token_source,
+            amount,
+            Clock::get()?.unix_timestamp,
+        ),
+    )?;
+
+    let volt_vault = **accounts.volt_vault;
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &volt_vault)?;
+
+    // claim the pending deposit of an older round first
+    let pending_deposit = &accounts.pending_deposit_info;
+    if pending_deposit.round_number != 0
+        && pending_deposit.round_number < volt_vault.round_number
+        && pending_deposit.num_underlying_deposited > 0
+    {
+        let mut pending_deposit_round =
+            Account::<Round>::try_from(&accounts.pending_deposit_round_info)?;
+        let pending_deposit_round_volt_tokens =
+            Account::<TokenAccount>::try_from(&accounts.pending_deposit_round_volt_tokens)?;
+        claim_volt_tokens(
+            &vault_signer,
+            &accounts.token_program.to_account_info(),
+            accounts.vault_authority.clone(),
+            &mut pending_deposit_round,
+            &pending_deposit_round_volt_tokens,
+            &mut accounts.pending_deposit_info,
+            accounts.vault_token_destination.to_account_info(),
+        )?;
+        pending_deposit_round.exit(&crate::id())?;
+    }
+
+    let user_authority = user_token_authority(
+        &accounts.underlying_token_source,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    );
+    deposit_underlying(
+        &volt_vault,
+        &vault_signer,
+        DepositTransfer {
+            token_program: accounts.token_program.to_account_info(),
+            vault_authority: accounts.vault_authority.clone(),
+            vault_mint: &accounts.vault_mint,
+            deposit_pool: &accounts.deposit_pool,
+            user_authority,
+            user_vault_tokens: accounts.vault_token_destination.to_account_info(),
+            user_ul_tokens: accounts.underlying_token_source.to_account_info(),
+            round_underlying_tokens: accounts.round_underlying_tokens.to_account_info(),
+        },
+        &mut accounts.round_info,
+        &mut accounts.pending_deposit_info,
+        &mut accounts.epoch_info,
+        amount,
+    )
+}
+
+// whitelisted volts must pass the whitelist as a remaining account, WithdrawWithClaim has no whitelist field
+pub(crate) fn withdraw_with_claim(ctx: Context<WithdrawWithClaim>, amount: u64) -> Result<()> {
+    let remaining_accounts = ctx.remaining_accounts;
+    let accounts = ctx.accounts;
+    check_authority_check(
+        &accounts.authority_check,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    )?;
+    let whitelist = load_whitelist(&accounts.extra_volt_data, remaining_accounts)?;
+    check(
+        PreflightVolt {
+            volt_vault: &accounts.volt_vault,
+            extra_volt_data: &accounts.extra_volt_data,
+            round: &accounts.round_info,
+            deposit_pool: &accounts.deposit_pool,
+            whitelist: whitelist.as_deref(),
+        }
+        .withdraw_with_claim(
+            &accounts.authority_check.key(),
+            Some(&**accounts.pending_withdrawal_info),
+            &accounts.vault_token_source,
+            &accounts.underlying_token_destination,
+            amount,
+            Clock::get()?.unix_timestamp,
+        ),
+    )?;
+
+    let volt_vault = **accounts.volt_vault;
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &volt_vault)?;
+
+    // claim the pending withdrawal of an older round first
+    let pending_withdrawal = &accounts.pending_withdrawal_info;
+    if pending_withdrawal.round_number != 0
+        && pending_withdrawal.round_number < volt_vault.round_number
+        && pending_withdrawal.num_volt_redeemed > 0
+    {
+        let mut pending_withdrawal_round =
+            Account::<Round>::try_from(&accounts.pending_withdrawal_round_info)?;
+        let pending_withdrawal_round_ul_pending = Account::<TokenAccount>::try_from(
+            &accounts.pending_withdrawal_round_underlying_tokens_for_pws,
+        )?;
+        claim_underlying(
+            &vault_signer,
+            &accounts.token_program.to_account_info(),
+            accounts.vault_authority.clone(),
+            &mut pending_withdrawal_round,
+            &pending_withdrawal_round_ul_pending,
+            &mut accounts.pending_withdrawal_info,
+            accounts.underlying_token_destination.to_account_info(),
+        )?;
+        pending_withdrawal_round.exit(&crate::id())?;
+    }
+
+    let user_authority = user_token_authority(
+        &accounts.vault_token_source,
+        &accounts.payer_authority,
+        &accounts.non_payer_authority,
+    );
+    withdraw_volt_tokens(
+        &volt_vault,
+        &vault_signer,
+        WithdrawTransfer {
+            token_program: accounts.token_program.to_account_info(),
+            vault_authority: accounts.vault_authority.clone(),
+            vault_mint: &accounts.vault_mint,
+            deposit_pool: &accounts.deposit_pool,
+            user_authority,
+            vault_token_source: accounts.vault_token_source.to_account_info(),
+            underlying_token_destination: accounts.underlying_token_destination.to_account_info(),
+        },
+        &mut accounts.round_info,
+        &mut accounts.pending_withdrawal_info,
+        &mut accounts.epoch_info,
+        amount,
+    )
+}
+
+pub(crate) fn claim_pending_deposit(ctx: Context<ClaimPendingDeposit>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check(check_claim_pending_deposit(
+        &accounts.volt_vault,
+        &accounts.extra_volt_data,
+        Some(&**accounts.pending_deposit_info),
+    ))?;
+
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &accounts.volt_vault)?;
+    claim_volt_tokens(
+        &vault_signer,
+        &accounts.token_program.to_account_info(),
+        accounts.vault_authority.clone(),
+        &mut accounts.pending_deposit_round_info,
+        &accounts.pending_deposit_round_volt_tokens,
+        &mut accounts.pending_deposit_info,
+        accounts.user_vault_tokens.to_account_info(),
+    )
+}
+
+pub(crate) fn claim_pending_withdrawal(ctx: Context<ClaimPendingWithdrawal>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check(check_claim_pending_withdrawal(
+        &accounts.volt_vault,
+        &accounts.extra_volt_data,
+        Some(&**accounts.pending_withdrawal_info),
+    ))?;
+
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &accounts.volt_vault)?;
+    claim_underlying(
+        &vault_signer,
+        &accounts.token_program.to_account_info(),
+        accounts.vault_authority.clone(),
+        &mut accounts.pending_withdrawal_round_info,
+        &accounts.round_underlying_tokens_for_pending_withdrawals,
+        &mut accounts.pending_withdrawal_info,
+        accounts.underlying_token_destination.to_account_info(),
+    )
+}
+
+pub(crate) fn cancel_pending_deposit(ctx: Context<CancelPendingDeposit>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check(check_cancel_pending_deposit(
+        &accounts.volt_vault,
+        &accounts.extra_volt_data,
+        Some(&**accounts.pending_deposit_info),
+    ))?;
+
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &accounts.volt_vault)?;
+    let num_underlying_deposited = accounts.pending_deposit_info.num_underlying_deposited;
+    let seeds = vault_signer.seeds();
+    transfer_tokens(
+        &accounts.token_program.to_account_info(),
+        accounts.round_underlying_tokens.to_account_info(),
+        accounts.underlying_token_destination.to_account_info(),
+        accounts.vault_authority.clone(),
+        &[&seeds[..]],
+        num_underlying_deposited,
+    )?;
+
+    let round = &mut accounts.round_info;
+    round.underlying_from_pending_deposits = checked_sub(
+        round.underlying_from_pending_deposits,
+        num_underlying_deposited,
+    )?;
+    let epoch_info = &mut accounts.epoch_info;
+    epoch_info.pending_deposits =
+        checked_sub(epoch_info.pending_deposits, num_underlying_deposited)?;
+    epoch_info.total_deposits = checked_sub(epoch_info.total_deposits, num_underlying_deposited)?;
+    epoch_info.canceled_deposits =
+        checked_add(epoch_info.canceled_deposits, num_underlying_deposited)?;
+    let pending_deposit = &mut accounts.pending_deposit_info;
+    pending_deposit.round_number = 0;
+    pending_deposit.num_underlying_deposited = 0;
+    Ok(())
+}
+
+pub(crate) fn cancel_pending_withdrawal(ctx: Context<CancelPendingWithdrawal>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check(check_cancel_pending_withdrawal(
+        &accounts.volt_vault,
+        &accounts.extra_volt_data,
+        Some(&**accounts.pending_withdrawal_info),
+    ))?;
+    require_keys_eq!(
+        accounts.vault_mint.key(),
+        accounts.volt_vault.vault_mint,
+        anchor_lang::error::ErrorCode::ConstraintAddress
+    );
+
+    // burned volt tokens are minted back
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &accounts.volt_vault)?;
+    let num_volt_redeemed = accounts.pending_withdrawal_info.num_volt_redeemed;
+    let seeds = vault_signer.seeds();
+    mint_tokens(
+        &accounts.token_program.to_account_info(),
+        accounts.vault_mint.to_account_info(),
+        accounts.vault_token_destination.to_account_info(),
+        accounts.vault_authority.clone(),
+        &[&seeds[..]],
+        num_volt_redeemed,
+    )?;
+
+    let round = &mut accounts.round_info;
+    round.volt_tokens_from_pending_withdrawals = checked_sub(
+        round.volt_tokens_from_pending_withdrawals,
+        num_volt_redeemed,
+    )?;
+    let epoch_info = &mut accounts.epoch_info;
+    epoch_info.pending_withdrawals_volt_tokens = checked_sub(
+        epoch_info.pending_withdrawals_volt_tokens,
+        num_volt_redeemed,
+    )?;
+    epoch_info.canceled_withdrawals =
+        checked_add(epoch_info.canceled_withdrawals, num_volt_redeemed)?;
+    let pending_withdrawal = &mut accounts.pending_withdrawal_info;
+    pending_withdrawal.round_number = 0;
+    pending_withdrawal.num_volt_redeemed = 0;
+    Ok(())
+}
+
+///////// round lifecycle ///////////
+
+// the volt program's keeper instructions are not part of this crate's ABI, so the mock rolls rounds with
+// its own mock_start_round/mock_end_round instructions, dispatched by entry below. their account lists
+// are only what the mock needs and do not match the volt program's start_round/end_round
+
+/// entrypoint of the mock program: mock_start_round/mock_end_round, otherwise the instructions in lib.rs
+pub fn entry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    try_entry(program_id, accounts, data).map_err(|e| {
+        e.log();
+        e.into()
+    })
+}
+
+fn try_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> Result<()> {
+    let sighash = data.get(..8);
+    if sighash == Some(&mock_start_round_sighash()[..]) {
+        let mut bumps = BTreeMap::new();
+        let mut remaining_accounts = accounts;
+        let mut round_accounts = MockStartRound::try_accounts(
+            program_id,
+            &mut remaining_accounts,
+            data,
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        start_round(Context::new(
+            program_id,
+            &mut round_accounts,
+            remaining_accounts,
+            bumps,
+        ))?;
+        round_accounts.exit(program_id)
+    } else if sighash == Some(&mock_end_round_sighash()[..]) {
+        let mut bumps = BTreeMap::new();
+        let mut remaining_accounts = accounts;
+        let mut round_accounts = MockEndRound::try_accounts(
+            program_id,
+            &mut remaining_accounts,
+            data,
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+        end_round(Context::new(
+            program_id,
+            &mut round_accounts,
+            remaining_accounts,
+            bumps,
+        ))?;
+        round_accounts.exit(program_id)
+    } else {
+        crate::entry(program_id, accounts, data).map_err(Into::into)
+    }
+}
+
+pub fn mock_start_round_sighash() -> [u8; 8] {
+    volt_instruction_sighash("mock_start_round")
+}
+
+pub fn mock_end_round_sighash() -> [u8; 8] {
+    volt_instruction_sighash("mock_end_round")
+}
+
+fn next_round_number(volt_vault: &VoltVault) -> Result<u64> {
+    checked_add(volt_vault.round_number, 1)
+}
+
+#[derive(Accounts)]
+pub struct MockStartRound<'info> {
+    // volt admin, validation happens in handler
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(
+        seeds = [
+            &volt_vault.key().to_bytes()[..],
+            EXTRA_VOLT_DATA_SEED
+        ],
+        bump,
+      )]
+    pub extra_volt_data: Box<Account<'info, ExtraVoltData>>,
+
+    /// CHECK: skip, checked by macro
+    #[account(address=volt_vault.vault_authority)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(address=volt_vault.vault_mint)]
+    pub vault_mint: Box<Account<'info, Mint>>,
+
+    #[account(address=volt_vault.underlying_asset_mint)]
+    pub underlying_asset_mint: Box<Account<'info, Mint>>,
+
+    #[account(address=volt_vault.deposit_pool)]
+    pub deposit_pool: Box<Account<'info, TokenAccount>>,
+
+    // round_number is incremented by the handler, so all round accounts are for the next round
+    #[account(init,
+        space=Round::LEN + 8,
+        seeds = [volt_vault.key().as_ref(), next_round_number(&volt_vault)?.to_le_bytes().as_ref(), ROUND_INFO_SEED],
+        bump,
+        payer = authority)]
+    pub round_info: Box<Account<'info, Round>>,
+
+    #[account(init,
+        seeds = [volt_vault.key().as_ref(), next_round_number(&volt_vault)?.to_le_bytes().as_ref(), ROUND_VOLT_TOKENS_SEED],
+        bump,
+        payer = authority,
+        token::mint = vault_mint,
+        token::authority = vault_authority)]
+    pub round_volt_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [volt_vault.key().as_ref(), next_round_number(&volt_vault)?.to_le_bytes().as_ref(), ROUND_UNDERLYING_TOKENS_SEED],
+        bump,
+        payer = authority,
+        token::mint = underlying_asset_mint,
+        token::authority = vault_authority)]
+    pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        seeds = [volt_vault.key().as_ref(), next_round_number(&volt_vault)?.to_le_bytes().as_ref(), ROUND_UL_PENDING_SEED],
+        bump,
+        payer = authority,
+        token::mint = underlying_asset_mint,
+        token::authority = vault_authority)]
+    pub round_underlying_tokens_for_pending_withdrawals: Box<Account<'info, TokenAccount>>,
+
+    #[account(init,
+        space=FriktionEpochInfo::LEN + 8,
+        seeds = [&volt_vault.key().to_bytes()[..], next_round_number(&volt_vault)?.to_le_bytes().as_ref(), EPOCH_INFO_SEED],
+        bump,
+        payer = authority)]
+    pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MockEndRound<'info> {
+    // volt admin, validation happens in handler
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub volt_vault: Box<Account<'info, VoltVault>>,
+
+    #[account(
+        seeds = [
+            &volt_vault.key().to_bytes()[..],
+            EXTRA_VOLT_DATA_SEED
+        ],
+        bump,
+      )]
+    pub extra_volt_data: Box<Account<'info, ExtraVoltData>>,
+
+    /// CHECK: skip, checked by macro
+    #[account(address=volt_vault.vault_authority)]
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address=volt_vault.vault_mint)]
+    pub vault_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut, address=volt_vault.deposit_pool)]
+    pub deposit_pool: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_INFO_SEED],
+        bump)]
+    pub round_info: Box<Account<'info, Round>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_VOLT_TOKENS_SEED],
+        bump)]
+    pub round_volt_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UNDERLYING_TOKENS_SEED],
+        bump)]
+    pub round_underlying_tokens: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut,
+        seeds = [volt_vault.key().as_ref(), volt_vault.round_number.to_le_bytes().as_ref() ,ROUND_UL_PENDING_SEED],
+        bump)]
+    pub round_underlying_tokens_for_pending_withdrawals: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [&volt_vault.key().to_bytes()[..], (volt_vault.round_number).to_le_bytes().as_ref() ,EPOCH_INFO_SEED],
+        bump,
+    )]
+    pub epoch_info: Box<Account<'info, FriktionEpochInfo>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub(crate) fn start_round(ctx: Context<MockStartRound>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check_admin(&accounts.authority.key(), &accounts.volt_vault)?;
+    check(check_phase(
+        &accounts.volt_vault,
+        &accounts.extra_volt_data,
+        InstructionKind::StartRound,
+    ))?;
+
+    let round_number = next_round_number(&accounts.volt_vault)?;
+    let underlying_pre_enter = accounts.deposit_pool.amount;
+
+    let volt_vault = &mut accounts.volt_vault;
+    volt_vault.round_number = round_number;
+    volt_vault.round_has_started = true;
+    volt_vault.total_underlying_pre_enter = underlying_pre_enter;
+
+    let round = &mut accounts.round_info;
+    round.number = round_number;
+    round.underlying_pre_enter = underlying_pre_enter;
+
+    let epoch_info = &mut accounts.epoch_info;
+    epoch_info.number = round_number;
+    epoch_info.underlying_pre_enter = underlying_pre_enter;
+    epoch_info.volt_token_supply = accounts.vault_mint.supply;
+    Ok(())
+}
+
+// settles pending withdrawals, then pending deposits, at the price of the deposit pool.
+// withdrawn volt tokens were already burned, so they are added back to the supply for pricing
+pub(crate) fn end_round(ctx: Context<MockEndRound>) -> Result<()> {
+    let accounts = ctx.accounts;
+    check_admin(&accounts.authority.key(), &accounts.volt_vault)?;
+    if !accounts.volt_vault.round_has_started {
+        return err!(ErrorCode::InvalidEndRoundState);
+    }
+
+    let vault_signer = VaultSigner::new(accounts.volt_vault.key(), &accounts.volt_vault)?;
+    let seeds = vault_signer.seeds();
+    let token_program = accounts.token_program.to_account_info();
+
+    let pending_withdrawal_volt_tokens = accounts.round_info.volt_tokens_from_pending_withdrawals;
+    let underlying_for_withdrawals = SharePrice {
+        total_underlying: accounts.deposit_pool.amount,
+        total_volt_tokens: checked_add(accounts.vault_mint.supply, pending_withdrawal_volt_tokens)?,
+    }
+    .underlying_for_volt_tokens(pending_withdrawal_volt_tokens)?;
+    transfer_tokens(
+        &token_program,
+        accounts.deposit_pool.to_account_info(),
+        accounts
+            .round_underlying_tokens_for_pending_withdrawals
+            .to_account_info(),
+        accounts.vault_authority.clone(),
+        &[&seeds[..]],
+        underlying_for_withdrawals,
+    )?;
+    accounts.deposit_pool.reload()?;
+
+    let pending_deposit_underlying = accounts.round_info.underlying_from_pending_deposits;
+    let volt_tokens_for_deposits =
+        SharePrice::from_balances(accounts.vault_mint.supply, accounts.deposit_pool.amount)
+            .volt_tokens_for_underlying(pending_deposit_underlying)?;
+    mint_tokens(
+        &token_program,
+        accounts.vault_mint.to_account_info(),
+        accounts.round_volt_tokens.to_account_info(),
+        accounts.vault_authority.clone(),
+        &[&seeds[..]],
+        volt_tokens_for_deposits,
+    )?;
+    transfer_tokens(
+        &token_program,
+        accounts.round_underlying_tokens.to_account_info(),
+        accounts.deposit_pool.to_account_info(),
+        accounts.vault_authority.clone(),
+        &[&seeds[..]],
+        pending_deposit_underlying,
+    )?;
+    accounts.deposit_pool.reload()?;
+    accounts.vault_mint.reload()?;
+
+    let underlying_post_settle = accounts.deposit_pool.amount;
+    let volt_token_supply = accounts.vault_mint.supply;
+
+    let volt_vault = &mut accounts.volt_vault;
+    volt_vault.total_underlying_post_settle = underlying_post_settle;
+    volt_vault.total_volt_tokens_post_settle = volt_token_supply;
+    volt_vault.round_has_started = false;
+    volt_vault.next_option_was_set = false;
+    volt_vault.prepare_is_finished = false;
+    volt_vault.enter_is_finished = false;
+    volt_vault.must_swap_premium_to_underlying = false;
+
+    accounts.round_info.underlying_post_settle = underlying_post_settle;
+
+    let epoch_info = &mut accounts.epoch_info;
+    epoch_info.underlying_post_settle = underlying_post_settle;
+    epoch_info.volt_token_supply = volt_token_supply;
+    epoch_info.vault_token_price = if volt_token_supply == 0 {
+        1.0
+    } else {
+        underlying_post_settle as f64 / volt_token_supply as f64
+    };
+    epoch_info.pending_withdrawals = underlying_for_withdrawals;
+    epoch_info.total_withdrawals =
+        checked_add(epoch_info.total_withdrawals, underlying_for_withdrawals)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pda::{PENDING_DEPOSIT_SEED, PENDING_WITHDRAWAL_SEED};
+    use crate::test_utils::{
+        account_info, mint_account, program_account, program_info, rent_sysvar_account,
+        set_test_syscall_stubs, set_token_amount, token_account, token_amount,
+    };
+    use anchor_lang::solana_program::entrypoint::ProgramResult;
+    use anchor_lang::solana_program::instruction::AccountMeta;
+    use anchor_lang::InstructionData;
+    use anchor_spl::token::spl_token;
+
+    // accounts shared by the instructions of a test, unknown keys are empty system accounts
+    #[derive(Default)]
+    struct Bank(BTreeMap<Pubkey, AccountInfo<'static>>);
+
+    impl Bank {
+        fn add(&mut self, info: AccountInfo<'static>) {
+            self.0.insert(*info.key, info);
+        }
+
+        fn get(&mut self, key: Pubkey) -> &AccountInfo<'static> {
+            self.0.entry(key).or_insert_with(|| {
+                account_info(key, anchor_lang::system_program::ID, vec![], false, false)
+            })
+        }
+
+        fn load<T: AccountDeserialize>(&mut self, key: Pubkey) -> T {
+            T::try_deserialize(&mut &self.get(key).data.borrow()[..]).unwrap()
+        }
+
+        fn process(&mut self, metas: Vec<AccountMeta>, data: Vec<u8>) -> ProgramResult {
+            let infos: Vec<AccountInfo<'static>> = metas
+                .iter()
+                .map(|meta| {
+                    let mut info = self.get(meta.pubkey).clone();
+                    info.is_signer = meta.is_signer;
+                    info.is_writable = meta.is_writable;
+                    info
+                })
+                .collect();
+            entry(&crate::id(), &infos, &data)
+        }
+    }
+
+    struct MockVolt {
+        bank: Bank,
+        admin: Pubkey,
+        volt: Pubkey,
+        volt_vault: VoltVault,
+        whitelist: Pubkey,
+        fee_acct: Pubkey,
+    }
+
+    struct User {
+        authority: Pubkey,
+        vault_tokens: Pubkey,
+        ul_tokens: Pubkey,
+    }
+
+    impl MockVolt {
+        fn new() -> Self {
+            set_test_syscall_stubs();
+            let volt = Pubkey::new_unique();
+            let (vault_authority, bump_authority) = find_mock_vault_authority_address(&volt);
+            let volt_vault = VoltVault {
+                admin_key: Pubkey::new_unique(),
+                vault_mint: Pubkey::new_unique(),
+                underlying_asset_mint: Pubkey::new_unique(),
+                deposit_pool: Pubkey::new_unique(),
+                writer_token_pool: Pubkey::new_unique(),
+                vault_authority,
+                bump_authority,
+                initialized: true,
+                ..VoltVault::default()
+            };
+            let whitelist = Pubkey::new_unique();
+            let extra_volt_data = ExtraVoltData {
+                whitelist,
+                ..ExtraVoltData::default()
+            };
+
+            let mut bank = Bank::default();
+            bank.add(program_account(volt, crate::id(), &volt_vault));
+            bank.add(program_account(
+                Self::pda(&volt, &[EXTRA_VOLT_DATA_SEED]),
+                crate::id(),
+                &extra_volt_data,
+            ));
+            bank.add(mint_account(volt_vault.vault_mint, vault_authority, 0));
+            bank.add(mint_account(
+                volt_vault.underlying_asset_mint,
+                Pubkey::new_unique(),
+                0,
+            ));
+            bank.add(token_account(
+                volt_vault.deposit_pool,
+                volt_vault.underlying_asset_mint,
+                vault_authority,
+                0,
+            ));
+            let fee_acct = Pubkey::new_unique();
+            bank.add(token_account(
+                fee_acct,
+                volt_vault.underlying_asset_mint,
+                Pubkey::new_unique(),
+                0,
+            ));
+            bank.add(program_info(anchor_lang::system_program::ID));
+            bank.add(program_info(spl_token::ID));
+            bank.add(rent_sysvar_account());
+            MockVolt {
+                bank,
+                admin: volt_vault.admin_key,
+                volt,
+                volt_vault,
+                whitelist,
+                fee_acct,
+            }
+        }
+
+        fn pda(volt: &Pubkey, seeds: &[&[u8]]) -> Pubkey {
+            let mut seeds = seeds.to_vec();
+            seeds.insert(0, volt.as_ref());
+            Pubkey::find_program_address(&seeds, &crate::id()).0
+        }
+
+        fn round_key(&self, round_number: u64, seed: &[u8]) -> Pubkey {
+            Self::pda(&self.volt, &[&round_number.to_le_bytes(), seed])
+        }
+
+        fn round_number(&mut self) -> u64 {
+            self.bank.load::<VoltVault>(self.volt).round_number
+        }
+
+        fn user(&mut self, underlying: u64) -> User {
+            let authority = Pubkey::new_unique();
+            let user = User {
+                authority,
+                vault_tokens: Pubkey::new_unique(),
+                ul_tokens: Pubkey::new_unique(),
+            };
+            self.bank.add(token_account(
+                user.vault_tokens,
+                self.volt_vault.vault_mint,
+                authority,
+                0,
+            ));
+            self.bank.add(token_account(
+                user.ul_tokens,
+                self.volt_vault.underlying_asset_mint,
+                authority,
+                underlying,
+            ));
+            user
+        }
+
+        fn amount(&mut self, token_account: Pubkey) -> u64 {
+            token_amount(self.bank.get(token_account))
+        }
+
+        fn start_round(&mut self) -> ProgramResult {
+            let round_number = self.round_number() + 1;
+            let metas = __client_accounts_mock_start_round::MockStartRound {
+                authority: self.admin,
+                volt_vault: self.volt,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                vault_authority: self.volt_vault.vault_authority,
+                vault_mint: self.volt_vault.vault_mint,
+                underlying_asset_mint: self.volt_vault.underlying_asset_mint,
+                deposit_pool: self.volt_vault.deposit_pool,
+                round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                round_volt_tokens: self.round_key(round_number, ROUND_VOLT_TOKENS_SEED),
+                round_underlying_tokens: self.round_key(round_number, ROUND_UNDERLYING_TOKENS_SEED),
+                round_underlying_tokens_for_pending_withdrawals: self
+                    .round_key(round_number, ROUND_UL_PENDING_SEED),
+                epoch_info: self.round_key(round_number, EPOCH_INFO_SEED),
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            }
+            .to_account_metas(None);
+            self.bank
+                .process(metas, mock_start_round_sighash().to_vec())
+        }
+
+        fn end_round(&mut self) -> ProgramResult {
+            let round_number = self.round_number();
+            let metas = __client_accounts_mock_end_round::MockEndRound {
+                authority: self.admin,
+                volt_vault: self.volt,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                vault_authority: self.volt_vault.vault_authority,
+                vault_mint: self.volt_vault.vault_mint,
+                deposit_pool: self.volt_vault.deposit_pool,
+                round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                round_volt_tokens: self.round_key(round_number, ROUND_VOLT_TOKENS_SEED),
+                round_underlying_tokens: self.round_key(round_number, ROUND_UNDERLYING_TOKENS_SEED),
+                round_underlying_tokens_for_pending_withdrawals: self
+                    .round_key(round_number, ROUND_UL_PENDING_SEED),
+                epoch_info: self.round_key(round_number, EPOCH_INFO_SEED),
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None);
+            self.bank.process(metas, mock_end_round_sighash().to_vec())
+        }
+
+        fn deposit(&mut self, user: &User, deposit_amount: u64) -> ProgramResult {
+            let round_number = self.round_number();
+            let metas = crate::accounts::Deposit {
+                payer_authority: user.authority,
+                non_payer_authority: user.authority,
+                authority_check: user.authority,
+                vault_mint: self.volt_vault.vault_mint,
+                volt_vault: self.volt,
+                vault_authority: self.volt_vault.vault_authority,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                whitelist: self.whitelist,
+                deposit_pool: self.volt_vault.deposit_pool,
+                writer_token_pool: self.volt_vault.writer_token_pool,
+                user_vault_tokens: user.vault_tokens,
+                user_ul_tokens: user.ul_tokens,
+                round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                round_volt_tokens: self.round_key(round_number, ROUND_VOLT_TOKENS_SEED),
+                round_underlying_tokens: self.round_key(round_number, ROUND_UNDERLYING_TOKENS_SEED),
+                pending_deposit_info: self.pending_deposit_key(user),
+                epoch_info: self.round_key(round_number, EPOCH_INFO_SEED),
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None);
+            self.bank
+                .process(metas, crate::instruction::Deposit { deposit_amount }.data())
+        }
+
+        fn withdraw(&mut self, user: &User, withdraw_amount: u64) -> ProgramResult {
+            let round_number = self.round_number();
+            let metas = crate::accounts::Withdraw {
+                payer_authority: user.authority,
+                non_payer_authority: user.authority,
+                authority_check: user.authority,
+                vault_mint: self.volt_vault.vault_mint,
+                volt_vault: self.volt,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                vault_authority: self.volt_vault.vault_authority,
+                whitelist: self.whitelist,
+                deposit_pool: self.volt_vault.deposit_pool,
+                underlying_token_destination: user.ul_tokens,
+                vault_token_source: user.vault_tokens,
+                round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                round_underlying_tokens: self.round_key(round_number, ROUND_UNDERLYING_TOKENS_SEED),
+                pending_withdrawal_info: self.pending_withdrawal_key(user),
+                epoch_info: self.round_key(round_number, EPOCH_INFO_SEED),
+                fee_acct: self.fee_acct,
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+                rent: anchor_lang::solana_program::sysvar::rent::ID,
+            }
+            .to_account_metas(None);
+            self.bank.process(
+                metas,
+                crate::instruction::Withdraw {
+                    amount: withdraw_amount,
+                }
+                .data(),
+            )
+        }
+
+        fn claim_pending_deposit(&mut self, user: &User) -> ProgramResult {
+            let pending_deposit_info = self.pending_deposit_key(user);
+            let round_number = self
+                .bank
+                .load::<PendingDeposit>(pending_deposit_info)
+                .round_number;
+            let metas = crate::accounts::ClaimPendingDeposit {
+                authority: user.authority,
+                volt_vault: self.volt,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                vault_authority: self.volt_vault.vault_authority,
+                user_vault_tokens: user.vault_tokens,
+                pending_deposit_round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                pending_deposit_round_volt_tokens: self
+                    .round_key(round_number, ROUND_VOLT_TOKENS_SEED),
+                pending_deposit_info,
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None);
+            self.bank
+                .process(metas, crate::instruction::ClaimPendingDeposit {}.data())
+        }
+
+        fn claim_pending_withdrawal(&mut self, user: &User) -> ProgramResult {
+            let pending_withdrawal_info = self.pending_withdrawal_key(user);
+            let round_number = self
+                .bank
+                .load::<PendingWithdrawal>(pending_withdrawal_info)
+                .round_number;
+            let metas = crate::accounts::ClaimPendingWithdrawal {
+                authority: user.authority,
+                volt_vault: self.volt,
+                extra_volt_data: Self::pda(&self.volt, &[EXTRA_VOLT_DATA_SEED]),
+                vault_authority: self.volt_vault.vault_authority,
+                vault_mint: self.volt_vault.vault_mint,
+                underlying_token_destination: user.ul_tokens,
+                pending_withdrawal_round_info: self.round_key(round_number, ROUND_INFO_SEED),
+                pending_withdrawal_info,
+                round_underlying_tokens_for_pending_withdrawals: self
+                    .round_key(round_number, ROUND_UL_PENDING_SEED),
+                system_program: anchor_lang::system_program::ID,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None);
+            self.bank
+                .process(metas, crate::instruction::ClaimPendingWithdrawal {}.data())
+        }
+
+        fn pending_deposit_key(&self, user: &User) -> Pubkey {
+            Self::pda(&self.volt, &[user.authority.as_ref(), PENDING_DEPOSIT_SEED])
+        }
+
+        fn pending_withdrawal_key(&self, user: &User) -> Pubkey {
+            Self::pda(
+                &self.volt,
+                &[user.authority.as_ref(), PENDING_WITHDRAWAL_SEED],
+            )
+        }
+    }
+
+    #[test]
+    fn deposit_round_roll_claim() {
+        let mut volt = MockVolt::new();
+        let user = volt.user(1_000);
+        volt.start_round().unwrap();
+
+        volt.deposit(&user, 100).unwrap();
+        assert_eq!(volt.amount(user.ul_tokens), 900);
+        let round_underlying_tokens = volt.round_key(1, ROUND_UNDERLYING_TOKENS_SEED);
+        assert_eq!(volt.amount(round_underlying_tokens), 100);
+        let pending_deposit: PendingDeposit = volt.bank.load(volt.pending_deposit_key(&user));
+        assert_eq!(pending_deposit.round_number, 1);
+        assert_eq!(pending_deposit.num_underlying_deposited, 100);
+        let round: Round = volt.bank.load(volt.round_key(1, ROUND_INFO_SEED));
+        assert_eq!(round.underlying_from_pending_deposits, 100);
+        assert_eq!(
+            volt.claim_pending_deposit(&user),
+            Err(error!(ErrorCode::InvalidClaimPendingState).into())
+        );
+
+        volt.end_round().unwrap();
+        assert_eq!(volt.amount(volt.volt_vault.deposit_pool), 100);
+        assert_eq!(volt.amount(round_underlying_tokens), 0);
+        assert_eq!(volt.amount(volt.round_key(1, ROUND_VOLT_TOKENS_SEED)), 100);
+        let epoch_info: FriktionEpochInfo = volt.bank.load(volt.round_key(1, EPOCH_INFO_SEED));
+        assert_eq!(epoch_info.total_deposits, 100);
+        assert_eq!(epoch_info.underlying_post_settle, 100);
+
+        volt.start_round().unwrap();
+        volt.claim_pending_deposit(&user).unwrap();
+        assert_eq!(volt.amount(user.vault_tokens), 100);
+        assert_eq!(volt.amount(volt.round_key(1, ROUND_VOLT_TOKENS_SEED)), 0);
+        let pending_deposit: PendingDeposit = volt.bank.load(volt.pending_deposit_key(&user));
+        assert_eq!(pending_deposit.round_number, 0);
+        assert_eq!(pending_deposit.num_underlying_deposited, 0);
+    }
+
+    #[test]
+    fn withdraw_round_roll_claim() {
+        let mut volt = MockVolt::new();
+        let depositor = volt.user(300);
+        let user = volt.user(1_000);
+        volt.start_round().unwrap();
+        volt.deposit(&depositor, 300).unwrap();
+        volt.deposit(&user, 100).unwrap();
+        volt.end_round().unwrap();
+        volt.start_round().unwrap();
+        volt.claim_pending_deposit(&user).unwrap();
+
+        // the pool doubles while the round is live
+        let deposit_pool = volt.volt_vault.deposit_pool;
+        set_token_amount(volt.bank.get(deposit_pool), 800);
+
+        volt.withdraw(&user, 40).unwrap();
+        assert_eq!(volt.amount(user.vault_tokens), 60);
+        let pending_withdrawal: PendingWithdrawal =
+            volt.bank.load(volt.pending_withdrawal_key(&user));
+        assert_eq!(pending_withdrawal.round_number, 2);
+        assert_eq!(pending_withdrawal.num_volt_redeemed, 40);
+        assert_eq!(
+            volt.withdraw(&user, 61),
+            Err(error!(ErrorCode::InsufficientVaultTokens).into())
+        );
+
+        volt.end_round().unwrap();
+        let round_ul_pending = volt.round_key(2, ROUND_UL_PENDING_SEED);
+        assert_eq!(volt.amount(round_ul_pending), 80);
+        assert_eq!(volt.amount(deposit_pool), 720);
+
+        volt.start_round().unwrap();
+        volt.claim_pending_withdrawal(&user).unwrap();
+        assert_eq!(volt.amount(user.ul_tokens), 980);
+        assert_eq!(volt.amount(round_ul_pending), 0);
+        let round: Round = volt.bank.load(volt.round_key(2, ROUND_INFO_SEED));
+        assert_eq!(round.volt_tokens_from_pending_withdrawals, 0);
+    }
+
+    #[test]
+    fn round_roll_is_admin_only() {
+        let mut volt = MockVolt::new();
+        volt.admin = Pubkey::new_unique();
+        assert_eq!(
+            volt.start_round(),
+            Err(error!(ErrorCode::InvalidAuthorityForPermissionedInstruction).into())
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::share_price::mul_div_floor;
+
 #[account]
 #[derive(Default)]
 pub struct Whitelist {
@@ -9,6 +12,20 @@ pub struct Whitelist {
     pub addresses: Vec<Pubkey>,
 }
 
+impl Whitelist {
+    // admin + length prefix of addresses
+    pub const BASE_LEN: usize = 32 + 4;
+
+    // space (w/o discriminator) needed to store num_addresses addresses
+    pub fn len_with_addresses(num_addresses: usize) -> usize {
+        Self::BASE_LEN + num_addresses * 32
+    }
+
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
 #[account]
 #[derive(Default)]
 /**
@@ -34,6 +51,26 @@ pub struct PendingDeposit {
 
 impl PendingDeposit {
     pub const LEN: usize = 17;
+
+    // round must be the Round this pending deposit is for, round_volt_tokens_balance the balance of its roundVoltTokens account.
+    // users receive their share (num_underlying_deposited / underlying_from_pending_deposits) of the round's volt tokens
+    pub fn claimable_volt_tokens(
+        &self,
+        round: &Round,
+        round_volt_tokens_balance: u64,
+    ) -> Result<u64> {
+        if self.round_number == 0 || self.num_underlying_deposited == 0 {
+            return Ok(0);
+        }
+        if self.round_number != round.number || round.underlying_from_pending_deposits == 0 {
+            return err!(ErrorCode::InvalidClaimPendingState);
+        }
+        mul_div_floor(
+            self.num_underlying_deposited,
+            round_volt_tokens_balance,
+            round.underlying_from_pending_deposits,
+        )
+    }
 }
 
 #[account]
@@ -61,6 +98,26 @@ pub struct PendingWithdrawal {
 
 impl PendingWithdrawal {
     pub const LEN: usize = 17;
+
+    // round must be the Round this pending withdrawal is for, round_ul_pending_balance the balance of its roundUlPending account.
+    // users receive their share (num_volt_redeemed / volt_tokens_from_pending_withdrawals) of the round's underlying
+    pub fn claimable_underlying(
+        &self,
+        round: &Round,
+        round_ul_pending_balance: u64,
+    ) -> Result<u64> {
+        if self.round_number == 0 || self.num_volt_redeemed == 0 {
+            return Ok(0);
+        }
+        if self.round_number != round.number || round.volt_tokens_from_pending_withdrawals == 0 {
+            return err!(ErrorCode::InvalidClaimPendingWithdrawalState);
+        }
+        mul_div_floor(
+            self.num_volt_redeemed,
+            round_ul_pending_balance,
+            round.volt_tokens_from_pending_withdrawals,
+        )
+    }
 }
 
 #[account]
@@ -78,13 +135,13 @@ impl UlOpenOrdersMetadata {
 pub struct EntropyRound {
     pub instant_deposits_native: u64,
     pub prev_entropy_account_deposits: u64,
-    pub initial_equity: f64,             //
-    pub new_equity_post_deposit: f64,    // 16
-    pub deposit_amt: f64,                // 24
-    pub withdraw_comp_from_deposit: u64, // 32
-    pub net_deposits: f64,               // 40
-    pub deposit_amt_native: u64,         // 48
-    pub withdraw_amt_native: u64,        // 56
+    pub initial_equity: f64,             // 24
+    pub new_equity_post_deposit: f64,    // 32
+    pub deposit_amt: f64,                // 40
+    pub withdraw_comp_from_deposit: u64, // 48
+    pub net_deposits: f64,               // 56
+    pub deposit_amt_native: u64,         // 64
+    pub withdraw_amt_native: u64,        // 72
     pub total_volt_supply: u64,
     pub oracle_price: f64,
 
@@ -93,25 +150,25 @@ pub struct EntropyRound {
     pub pnl_quote: f64,
     pub performance_fees_quote: f64,
 
-    pub temp1: Pubkey,        // 368
-    pub temp2: Pubkey,        // 400
-    pub temp3: Pubkey,        // 432
-    pub extra_key_11: Pubkey, // 464
-    pub extra_key_12: Pubkey, // 496
-
-    pub unused_uint_four: u64, // 504
-    pub unused_uint_five: u64, // 512
-    pub unused_uint_six: u64,  // 520
-    pub unused_uint_12: u64,   // 568
-
-    pub unused_float1: f64, // 504
-    pub unused_float2: f64, // 512
-    pub unused_float3: f64, // 520
-    pub unused_float4: f64, // 568
-
-    pub unused_bool_one: bool,   // 569
-    pub unused_bool_two: bool,   // 570
-    pub unused_bool_three: bool, // 571
+    pub temp1: Pubkey,        // 152
+    pub temp2: Pubkey,        // 184
+    pub temp3: Pubkey,        // 216
+    pub extra_key_11: Pubkey, // 248
+    pub extra_key_12: Pubkey, // 280
+
+    pub unused_uint_four: u64, // 288
+    pub unused_uint_five: u64, // 296
+    pub unused_uint_six: u64,  // 304
+    pub unused_uint_12: u64,   // 312
+
+    pub unused_float1: f64, // 320
+    pub unused_float2: f64, // 328
+    pub unused_float3: f64, // 336
+    pub unused_float4: f64, // 344
+
+    pub unused_bool_one: bool,   // 345
+    pub unused_bool_two: bool,   // 346
+    pub unused_bool_three: bool, // 347
     pub unused_bool_four: bool,
 }
 
@@ -230,48 +287,48 @@ pub struct EntropyMetadata {
     // basis volt stuff
     pub required_basis_from_oracle: f64,
 
-    pub extra_key_3: Pubkey,  // 368
-    pub extra_key_4: Pubkey,  // 400
-    pub extra_key_5: Pubkey,  // 432
-    pub extra_key_6: Pubkey,  // 464
-    pub extra_key_7: Pubkey,  // 496
-    pub extra_key_8: Pubkey,  // 368
-    pub extra_key_9: Pubkey,  // 400
-    pub extra_key_10: Pubkey, // 432
-    pub extra_key_11: Pubkey, // 464
-    pub extra_key_12: Pubkey, // 496
-
-    pub unused_uint_four: u64, // 504
-    pub unused_uint_five: u64, // 512
-    pub unused_uint_six: u64,  // 520
-    pub unused_uint_12: u64,   // 568
-    pub unused_uint_123: u64,  // 504
-    pub unused_uint_456: u64,  // 512
-    pub unused_uint_789: u64,  // 520
-    pub unused_uint_102: u64,  // 568
-
-    pub unused_float1: f64,  // 504
-    pub unused_float2: f64,  // 512
-    pub unused_float3: f64,  // 520
-    pub unused_float4: f64,  // 568
-    pub unused_float5: f64,  // 504
-    pub unused_float6: f64,  // 512
-    pub unused_float7: f64,  // 520
-    pub unused_float8: f64,  // 568
-    pub unused_float9: f64,  // 504
-    pub unused_float10: f64, // 512
-    pub unused_float11: f64, // 520
-    pub unused_float12: f64, // 568
-
-    pub unused_bool_one: bool,   // 569
-    pub unused_bool_two: bool,   // 570
-    pub unused_bool_three: bool, // 571
+    pub extra_key_3: Pubkey,  // 56
+    pub extra_key_4: Pubkey,  // 88
+    pub extra_key_5: Pubkey,  // 120
+    pub extra_key_6: Pubkey,  // 152
+    pub extra_key_7: Pubkey,  // 184
+    pub extra_key_8: Pubkey,  // 216
+    pub extra_key_9: Pubkey,  // 248
+    pub extra_key_10: Pubkey, // 280
+    pub extra_key_11: Pubkey, // 312
+    pub extra_key_12: Pubkey, // 344
+
+    pub unused_uint_four: u64, // 352
+    pub unused_uint_five: u64, // 360
+    pub unused_uint_six: u64,  // 368
+    pub unused_uint_12: u64,   // 376
+    pub unused_uint_123: u64,  // 384
+    pub unused_uint_456: u64,  // 392
+    pub unused_uint_789: u64,  // 400
+    pub unused_uint_102: u64,  // 408
+
+    pub unused_float1: f64,  // 416
+    pub unused_float2: f64,  // 424
+    pub unused_float3: f64,  // 432
+    pub unused_float4: f64,  // 440
+    pub unused_float5: f64,  // 448
+    pub unused_float6: f64,  // 456
+    pub unused_float7: f64,  // 464
+    pub unused_float8: f64,  // 472
+    pub unused_float9: f64,  // 480
+    pub unused_float10: f64, // 488
+    pub unused_float11: f64, // 496
+    pub unused_float12: f64, // 504
+
+    pub unused_bool_one: bool,   // 505
+    pub unused_bool_two: bool,   // 506
+    pub unused_bool_three: bool, // 507
     pub unused_bool_four: bool,
-    pub unused_bool_five: bool,  // 569
-    pub unused_bool_six: bool,   // 570
-    pub unused_bool_seven: bool, // 571
+    pub unused_bool_five: bool,  // 509
+    pub unused_bool_six: bool,   // 510
+    pub unused_bool_seven: bool, // 511
     pub unused_bool_eight: bool,
-    pub unused_bool_nine: bool, // 571
+    pub unused_bool_nine: bool, // 513
     pub unused_bool_ten: bool,
 
     pub vault_name: String,
@@ -302,36 +359,36 @@ pub struct ExtraVoltData {
     pub target_leverage_lenience: f64, // 114
 
     // leverage threshold for calling exit_early instructoin
-    pub exit_early_ratio: f64, // 130
+    pub exit_early_ratio: f64, // 122
 
     // is this trading on mango or entropy (or 01 :P)?
-    pub entropy_program_id: Pubkey, // 194
+    pub entropy_program_id: Pubkey, // 154
 
     // group the protocol trades on
-    pub entropy_group: Pubkey, // 226 186
+    pub entropy_group: Pubkey, // 186
 
     // account the program initializes to trade with
-    pub entropy_account: Pubkey, // 258 218
+    pub entropy_account: Pubkey, // 218
 
     // pubkey of perp market to trade
-    pub power_perp_market: Pubkey, // 322
+    pub power_perp_market: Pubkey, // 250
 
     // true after settle_deposits was called successfully for current round
-    pub have_resolved_deposits: bool, // 355
+    pub have_resolved_deposits: bool, // 251
 
     // true after obtained target collateralization, ready to end round
-    pub done_rebalancing: bool, // 356
+    pub done_rebalancing: bool, // 252
 
-    pub non_payer_authority: Pubkey, // 388
-    pub serum_program_id: Pubkey,    // 420
-    pub entropy_cache: Pubkey,       // 348 (actual)
+    pub non_payer_authority: Pubkey, // 284
+    pub serum_program_id: Pubkey,    // 316
+    pub entropy_cache: Pubkey,       // 348
     /// pubkey of perp market to hedge
-    pub spot_perp_market: Pubkey, // 354 (380)
-    pub extra_key_7: Pubkey,         // 516  412
-    pub extra_key_8: Pubkey,         // 548 444
-    pub extra_key_9: Pubkey,         // 580
-    pub extra_key_10: Pubkey,        // 612
-    pub extra_key_11: Pubkey,        // 644
+    pub spot_perp_market: Pubkey, // 380
+    pub extra_key_7: Pubkey,         // 412
+    pub extra_key_8: Pubkey,         // 444
+    pub extra_key_9: Pubkey,         // 476
+    pub extra_key_10: Pubkey,        // 508
+    pub extra_key_11: Pubkey,        // 540
     pub extra_key_12: Pubkey,
     pub extra_key_13: Pubkey,
     pub extra_key_14: Pubkey, // 636
@@ -339,22 +396,22 @@ pub struct ExtraVoltData {
     pub net_withdrawals: u64,      // 644
     pub max_quote_pos_change: u64, // 652
     // defines width of dollar delta range hedge must lie within
-    pub target_hedge_lenience: f64, // 122
-    pub unused_uint_four: u64,      // 676
-    pub unused_uint_five: u64,      // 684
-    pub unused_uint_six: u64,       // 692
-    pub unused_uint_7: u64,         // 700
-    pub unused_uint_8: u64,         // 708
-    pub unused_uint_9: u64,         // 716
-    pub unused_uint_10: u64,        // 724
-    pub unused_uint_11: u64,        //
+    pub target_hedge_lenience: f64, // 660
+    pub unused_uint_four: u64,      // 668
+    pub unused_uint_five: u64,      // 676
+    pub unused_uint_six: u64,       // 684
+    pub unused_uint_7: u64,         // 692
+    pub unused_uint_8: u64,         // 700
+    pub unused_uint_9: u64,         // 708
+    pub unused_uint_10: u64,        // 716
+    pub unused_uint_11: u64,        // 724
     pub unused_uint_12: u64,        // 732
 
-    pub turn_off_deposits_and_withdrawals: bool, //
-    pub rebalance_is_ready: bool,                //
-    pub unused_bool1234: bool,                   //
-    pub done_rebalancing_power_perp: bool,       //
-    pub is_hedging_on: bool,                     //
+    pub turn_off_deposits_and_withdrawals: bool, // 733
+    pub rebalance_is_ready: bool,                // 734
+    pub unused_bool1234: bool,                   // 735
+    pub done_rebalancing_power_perp: bool,       // 736
+    pub is_hedging_on: bool,                     // 737
     pub have_taken_performance_fees: bool,       // 738
 }
 
@@ -538,6 +595,72 @@ pub struct VoltVault {
 
 impl VoltVault {
     pub const LEN: usize = 739;
+
+    pub fn volt_type(&self) -> VoltType {
+        VoltType::from(self.vault_type)
+    }
+}
+
+/**
+ * Strategy of a volt, stored as VoltVault.vault_type.
+ *  NOTES:
+ *  1. only the vault_type values the volt program branches on are named (the volt-specific deposit accounts in remaining_accounts.rs)
+ *  2. the CashSecuredPut and Dca variants asked for in user-004 are not included, pending the requester's confirmation of their vault_type values.
+ *     neither this crate nor its CHANGELOG publishes one (only the InvalidEndDcaRoundState error hints at DCA volts), so such volts decode as Unknown
+ *  3. Unknown keeps the raw value so that volt types added to the program later still round-trip
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoltType {
+    // covered calls
+    ShortOptions,
+    // entropy (crab, basis)
+    Entropy,
+    PrincipalProtection,
+    Unknown(u64),
+}
+
+impl VoltType {
+    pub const SHORT_OPTIONS: u64 = 0;
+    pub const ENTROPY: u64 = 1;
+    pub const PRINCIPAL_PROTECTION: u64 = 2;
+
+    pub fn is_short_options(&self) -> bool {
+        *self == VoltType::ShortOptions
+    }
+
+    pub fn is_entropy(&self) -> bool {
+        *self == VoltType::Entropy
+    }
+
+    pub fn is_principal_protection(&self) -> bool {
+        *self == VoltType::PrincipalProtection
+    }
+
+    pub fn is_known(&self) -> bool {
+        !matches!(self, VoltType::Unknown(_))
+    }
+}
+
+impl From<u64> for VoltType {
+    fn from(vault_type: u64) -> Self {
+        match vault_type {
+            VoltType::SHORT_OPTIONS => VoltType::ShortOptions,
+            VoltType::ENTROPY => VoltType::Entropy,
+            VoltType::PRINCIPAL_PROTECTION => VoltType::PrincipalProtection,
+            other => VoltType::Unknown(other),
+        }
+    }
+}
+
+impl From<VoltType> for u64 {
+    fn from(volt_type: VoltType) -> Self {
+        match volt_type {
+            VoltType::ShortOptions => VoltType::SHORT_OPTIONS,
+            VoltType::Entropy => VoltType::ENTROPY,
+            VoltType::PrincipalProtection => VoltType::PRINCIPAL_PROTECTION,
+            VoltType::Unknown(other) => other,
+        }
+    }
 }
 
 #[account]
@@ -770,3 +893,130 @@ pub struct LendingKeys {
     pub solend_vault: Pubkey,
     pub tulip_vault: Pubkey,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volt_type_round_trips() {
+        for vault_type in 0..8u64 {
+            assert_eq!(u64::from(VoltType::from(vault_type)), vault_type);
+        }
+        assert_eq!(VoltType::from(0), VoltType::ShortOptions);
+        assert_eq!(VoltType::from(1), VoltType::Entropy);
+        assert_eq!(VoltType::from(2), VoltType::PrincipalProtection);
+        assert_eq!(VoltType::from(3), VoltType::Unknown(3));
+        assert!(!VoltType::from(3).is_known());
+    }
+
+    #[test]
+    fn volt_vault_volt_type() {
+        let volt_vault = VoltVault {
+            vault_type: VoltType::ENTROPY,
+            ..VoltVault::default()
+        };
+        assert!(volt_vault.volt_type().is_entropy());
+        assert!(!volt_vault.volt_type().is_short_options());
+        assert!(VoltVault::default().volt_type().is_short_options());
+    }
+
+    #[test]
+    fn whitelist_membership_and_size() {
+        let whitelisted = Pubkey::new_unique();
+        let whitelist = Whitelist {
+            admin: Pubkey::new_unique(),
+            addresses: vec![whitelisted, Pubkey::new_unique()],
+        };
+        assert!(whitelist.contains(&whitelisted));
+        assert!(!whitelist.contains(&Pubkey::new_unique()));
+        assert!(!whitelist.contains(&whitelist.admin));
+
+        assert_eq!(Whitelist::len_with_addresses(0), Whitelist::BASE_LEN);
+        assert_eq!(
+            Whitelist::len_with_addresses(whitelist.addresses.len()),
+            whitelist.try_to_vec().unwrap().len()
+        );
+    }
+
+    fn round(number: u64) -> Round {
+        Round {
+            number,
+            underlying_from_pending_deposits: 300,
+            volt_tokens_from_pending_withdrawals: 400,
+            ..Round::default()
+        }
+    }
+
+    #[test]
+    fn claimable_volt_tokens() {
+        let pending_deposit = PendingDeposit {
+            initialized: true,
+            round_number: 4,
+            num_underlying_deposited: 100,
+        };
+        // 100 / 300 of 1000 volt tokens, rounded down
+        assert_eq!(
+            pending_deposit
+                .claimable_volt_tokens(&round(4), 1_000)
+                .unwrap(),
+            333
+        );
+        assert_eq!(
+            PendingDeposit::default()
+                .claimable_volt_tokens(&round(4), 1_000)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            pending_deposit
+                .claimable_volt_tokens(&round(5), 1_000)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidClaimPendingState)
+        );
+        let empty_round = Round {
+            underlying_from_pending_deposits: 0,
+            ..round(4)
+        };
+        assert_eq!(
+            pending_deposit
+                .claimable_volt_tokens(&empty_round, 1_000)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidClaimPendingState)
+        );
+    }
+
+    #[test]
+    fn claimable_underlying() {
+        let pending_withdrawal = PendingWithdrawal {
+            initialized: true,
+            round_number: 4,
+            num_volt_redeemed: 100,
+        };
+        // 100 / 400 of 999 underlying, rounded down
+        assert_eq!(
+            pending_withdrawal
+                .claimable_underlying(&round(4), 999)
+                .unwrap(),
+            249
+        );
+        // the whole round's pending withdrawals claim the whole balance
+        let all = PendingWithdrawal {
+            num_volt_redeemed: 400,
+            ..pending_withdrawal
+        };
+        assert_eq!(all.claimable_underlying(&round(4), 999).unwrap(), 999);
+        assert_eq!(
+            PendingWithdrawal::default()
+                .claimable_underlying(&round(4), 999)
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            pending_withdrawal
+                .claimable_underlying(&round(3), 999)
+                .unwrap_err(),
+            error!(ErrorCode::InvalidClaimPendingWithdrawalState)
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey::PubkeyError;
+
+// seeds used by the volt program. every seed scheme is prefixed by the volt vault key
+pub const EXTRA_VOLT_DATA_SEED: &[u8] = b"extraVoltData";
+pub const ROUND_INFO_SEED: &[u8] = b"roundInfo";
+pub const ROUND_VOLT_TOKENS_SEED: &[u8] = b"roundVoltTokens";
+pub const ROUND_UNDERLYING_TOKENS_SEED: &[u8] = b"roundUnderlyingTokens";
+pub const ROUND_UL_PENDING_SEED: &[u8] = b"roundUlPending";
+pub const EPOCH_INFO_SEED: &[u8] = b"epochInfo";
+pub const PENDING_DEPOSIT_SEED: &[u8] = b"pendingDeposit";
+pub const PENDING_WITHDRAWAL_SEED: &[u8] = b"pendingWithdrawal";
+pub const PROTECTION_VAULT_SEED: &[u8] = b"protectionVault";
+
+fn program_id_or_default(program_id: Option<&Pubkey>) -> Pubkey {
+    program_id.copied().unwrap_or_else(crate::id)
+}
+
+fn find_volt_address(volt: &Pubkey, seed: &[u8], program_id: Option<&Pubkey>) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[volt.as_ref(), seed], &program_id_or_default(program_id))
+}
+
+fn create_volt_address(
+    volt: &Pubkey,
+    seed: &[u8],
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &[volt.as_ref(), seed, &[bump]],
+        &program_id_or_default(program_id),
+    )
+}
+
+// round number is always encoded little-endian, see contexts.rs
+fn find_round_address(
+    volt: &Pubkey,
+    round_number: u64,
+    seed: &[u8],
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[volt.as_ref(), round_number.to_le_bytes().as_ref(), seed],
+        &program_id_or_default(program_id),
+    )
+}
+
+fn create_round_address(
+    volt: &Pubkey,
+    round_number: u64,
+    seed: &[u8],
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &[
+            volt.as_ref(),
+            round_number.to_le_bytes().as_ref(),
+            seed,
+            &[bump],
+        ],
+        &program_id_or_default(program_id),
+    )
+}
+
+fn find_user_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    seed: &[u8],
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[volt.as_ref(), user.as_ref(), seed],
+        &program_id_or_default(program_id),
+    )
+}
+
+fn create_user_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    seed: &[u8],
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    Pubkey::create_program_address(
+        &[volt.as_ref(), user.as_ref(), seed, &[bump]],
+        &program_id_or_default(program_id),
+    )
+}
+
+///////// per-volt PDAs ///////////
+
+pub fn find_extra_volt_data_address(volt: &Pubkey, program_id: Option<&Pubkey>) -> (Pubkey, u8) {
+    find_volt_address(volt, EXTRA_VOLT_DATA_SEED, program_id)
+}
+
+pub fn create_extra_volt_data_address(
+    volt: &Pubkey,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_volt_address(volt, EXTRA_VOLT_DATA_SEED, bump, program_id)
+}
+
+pub fn find_protection_vault_address(volt: &Pubkey, program_id: Option<&Pubkey>) -> (Pubkey, u8) {
+    find_volt_address(volt, PROTECTION_VAULT_SEED, program_id)
+}
+
+pub fn create_protection_vault_address(
+    volt: &Pubkey,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_volt_address(volt, PROTECTION_VAULT_SEED, bump, program_id)
+}
+
+///////// per-round PDAs ///////////
+
+pub fn find_round_info_address(
+    volt: &Pubkey,
+    round_number: u64,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_round_address(volt, round_number, ROUND_INFO_SEED, program_id)
+}
+
+pub fn create_round_info_address(
+    volt: &Pubkey,
+    round_number: u64,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_round_address(volt, round_number, ROUND_INFO_SEED, bump, program_id)
+}
+
+pub fn find_round_volt_tokens_address(
+    volt: &Pubkey,
+    round_number: u64,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_round_address(volt, round_number, ROUND_VOLT_TOKENS_SEED, program_id)
+}
+
+pub fn create_round_volt_tokens_address(
+    volt: &Pubkey,
+    round_number: u64,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_round_address(volt, round_number, ROUND_VOLT_TOKENS_SEED, bump, program_id)
+}
+
+pub fn find_round_underlying_tokens_address(
+    volt: &Pubkey,
+    round_number: u64,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_round_address(volt, round_number, ROUND_UNDERLYING_TOKENS_SEED, program_id)
+}
+
+pub fn create_round_underlying_tokens_address(
+    volt: &Pubkey,
+    round_number: u64,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_round_address(
+        volt,
+        round_number,
+        ROUND_UNDERLYING_TOKENS_SEED,
+        bump,
+        program_id,
+    )
+}
+
+pub fn find_round_ul_pending_address(
+    volt: &Pubkey,
+    round_number: u64,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_round_address(volt, round_number, ROUND_UL_PENDING_SEED, program_id)
+}
+
+pub fn create_round_ul_pending_address(
+    volt: &Pubkey,
+    round_number: u64,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_round_address(volt, round_number, ROUND_UL_PENDING_SEED, bump, program_id)
+}
+
+pub fn find_epoch_info_address(
+    volt: &Pubkey,
+    round_number: u64,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_round_address(volt, round_number, EPOCH_INFO_SEED, program_id)
+}
+
+pub fn create_epoch_info_address(
+    volt: &Pubkey,
+    round_number: u64,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_round_address(volt, round_number, EPOCH_INFO_SEED, bump, program_id)
+}
+
+///////// per-user PDAs ///////////
+
+// user == authority_check in Deposit/Withdraw, == authority in the claim/cancel instructions
+pub fn find_pending_deposit_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_user_address(volt, user, PENDING_DEPOSIT_SEED, program_id)
+}
+
+pub fn create_pending_deposit_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_user_address(volt, user, PENDING_DEPOSIT_SEED, bump, program_id)
+}
+
+pub fn find_pending_withdrawal_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    program_id: Option<&Pubkey>,
+) -> (Pubkey, u8) {
+    find_user_address(volt, user, PENDING_WITHDRAWAL_SEED, program_id)
+}
+
+pub fn create_pending_withdrawal_address(
+    volt: &Pubkey,
+    user: &Pubkey,
+    bump: u8,
+    program_id: Option<&Pubkey>,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    create_user_address(volt, user, PENDING_WITHDRAWAL_SEED, bump, program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_and_create_agree() {
+        let volt = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        let (key, bump) = find_extra_volt_data_address(&volt, None);
+        assert_eq!(create_extra_volt_data_address(&volt, bump, None), Ok(key));
+
+        let (key, bump) = find_round_info_address(&volt, 7, None);
+        assert_eq!(create_round_info_address(&volt, 7, bump, None), Ok(key));
+
+        let (key, bump) = find_pending_deposit_address(&volt, &user, None);
+        assert_eq!(
+            create_pending_deposit_address(&volt, &user, bump, None),
+            Ok(key)
+        );
+
+        let (key, bump) = find_pending_withdrawal_address(&volt, &user, None);
+        assert_eq!(
+            create_pending_withdrawal_address(&volt, &user, bump, None),
+            Ok(key)
+        );
+    }
+
+    #[test]
+    fn seeds_match_program() {
+        let volt = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        assert_eq!(
+            find_extra_volt_data_address(&volt, None),
+            Pubkey::find_program_address(&[volt.as_ref(), b"extraVoltData"], &crate::ID)
+        );
+        assert_eq!(
+            find_epoch_info_address(&volt, 258, None),
+            Pubkey::find_program_address(
+                &[volt.as_ref(), &[2, 1, 0, 0, 0, 0, 0, 0], b"epochInfo"],
+                &crate::ID
+            )
+        );
+        assert_eq!(
+            find_pending_deposit_address(&volt, &user, None),
+            Pubkey::find_program_address(
+                &[volt.as_ref(), user.as_ref(), b"pendingDeposit"],
+                &crate::ID
+            )
+        );
+    }
+
+    #[test]
+    fn program_id_override() {
+        let volt = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+
+        assert_eq!(
+            find_round_info_address(&volt, 1, Some(&crate::ID)),
+            find_round_info_address(&volt, 1, None)
+        );
+        assert_eq!(
+            find_round_info_address(&volt, 1, Some(&program_id)),
+            Pubkey::find_program_address(
+                &[volt.as_ref(), &1u64.to_le_bytes(), ROUND_INFO_SEED],
+                &program_id
+            )
+        );
+        assert_ne!(
+            find_round_info_address(&volt, 1, Some(&program_id)),
+            find_round_info_address(&volt, 1, None)
+        );
+    }
+}
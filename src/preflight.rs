@@ -0,0 +1,781 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+
+use crate::error::ErrorCode;
+use crate::{
+    ExtraVoltData, InstructionKind, PendingDeposit, PendingWithdrawal, Round, RoundPhase,
+    VoltVault, Whitelist,
+};
+
+pub type PreflightResult = std::result::Result<(), ErrorCode>;
+
+// PreflightResult as the anchor error the program fails with
+pub(crate) fn check(result: PreflightResult) -> Result<()> {
+    result.map_err(|error_code| error!(error_code))
+}
+
+/**
+ * Volt state the program reads when validating user instructions.
+ * Each check returns the ErrorCode the program would fail the transaction with, checked in roughly the same order as the program.
+ *  NOTES:
+ *  1. round must be the Round account for volt_vault.round_number
+ *  2. whitelist is only read if extra_volt_data.is_whitelisted
+ *  3. token accounts are the unpacked spl token accounts (anchor's TokenAccount derefs to this)
+ */
+pub struct PreflightVolt<'a> {
+    pub volt_vault: &'a VoltVault,
+    pub extra_volt_data: &'a ExtraVoltData,
+    pub round: &'a Round,
+    pub deposit_pool: &'a SplTokenAccount,
+    pub whitelist: Option<&'a Whitelist>,
+}
+
+pub(crate) fn check_phase(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    ix: InstructionKind,
+) -> PreflightResult {
+    let phase = RoundPhase::derive(volt_vault, extra_volt_data)?;
+    match phase.illegal_instruction_error(ix) {
+        Some(error_code) => Err(error_code),
+        None => Ok(()),
+    }
+}
+
+// error the program fails deposits/withdrawals with outside of the transfer window
+fn invalid_time_error(ix: InstructionKind) -> ErrorCode {
+    match ix {
+        InstructionKind::Withdraw | InstructionKind::WithdrawWithClaim => {
+            ErrorCode::InvalidWithdrawalTime
+        }
+        _ => ErrorCode::InvalidDepositTime,
+    }
+}
+
+/**
+ * Deposits and withdrawals are only allowed within the transfer window.
+ *  NOTES:
+ *  1. the window opens at start_transfer_time and closes at start_transfer_time + transfer_window,
+ *     or at end_transfer_time if that is later (end_transfer_time is the minimum end of the window)
+ *  2. a volt with neither transfer_window nor end_transfer_time set has no end to the window
+ *  3. a negative unix_timestamp is before any window, so it is rejected
+ */
+pub(crate) fn check_transfer_window(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    ix: InstructionKind,
+    unix_timestamp: i64,
+) -> PreflightResult {
+    if extra_volt_data.turn_off_deposits_and_withdrawals {
+        return Err(ErrorCode::DepositsAndWithdrawalsAreTurnedOff);
+    }
+
+    let now = u64::try_from(unix_timestamp).map_err(|_| invalid_time_error(ix))?;
+    if now < volt_vault.start_transfer_time {
+        return Err(invalid_time_error(ix));
+    }
+
+    if volt_vault.transfer_window > 0 || volt_vault.end_transfer_time > 0 {
+        let window_end = volt_vault
+            .start_transfer_time
+            .saturating_add(volt_vault.transfer_window)
+            .max(volt_vault.end_transfer_time);
+        if now > window_end {
+            return Err(invalid_time_error(ix));
+        }
+    }
+    Ok(())
+}
+
+/// whether user may deposit into (or withdraw from) the volt. whitelist must be the account at
+/// extra_volt_data.whitelist, and is only required if extra_volt_data.is_whitelisted
+pub fn check_whitelist(
+    extra_volt_data: &ExtraVoltData,
+    whitelist: Option<&Whitelist>,
+    user: &Pubkey,
+) -> PreflightResult {
+    if !extra_volt_data.is_whitelisted {
+        return Ok(());
+    }
+    match whitelist {
+        Some(whitelist) if whitelist.contains(user) => Ok(()),
+        Some(_) => Err(ErrorCode::NonWhitelistedUser),
+        None => Err(ErrorCode::InvalidWhitelistAccountVector),
+    }
+}
+
+impl<'a> PreflightVolt<'a> {
+    fn check_phase(&self, ix: InstructionKind) -> PreflightResult {
+        check_phase(self.volt_vault, self.extra_volt_data, ix)
+    }
+
+    fn check_transfer_window(&self, ix: InstructionKind, unix_timestamp: i64) -> PreflightResult {
+        check_transfer_window(self.volt_vault, self.extra_volt_data, ix, unix_timestamp)
+    }
+
+    fn check_whitelist(&self, user: &Pubkey) -> PreflightResult {
+        check_whitelist(self.extra_volt_data, self.whitelist, user)
+    }
+
+    fn check_user_mints(
+        &self,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+    ) -> PreflightResult {
+        if user_vault_tokens.mint != self.volt_vault.vault_mint {
+            return Err(ErrorCode::VaultMintDoesNotMatchUserTokenAccount);
+        }
+        if user_ul_tokens.mint != self.deposit_pool.mint {
+            return Err(ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount);
+        }
+        Ok(())
+    }
+
+    // pending deposit made in the current round, if any
+    fn current_round_pending_deposit(&self, pending_deposit: Option<&PendingDeposit>) -> u64 {
+        match pending_deposit {
+            Some(pd) if pd.round_number == self.volt_vault.round_number => {
+                pd.num_underlying_deposited
+            }
+            _ => 0,
+        }
+    }
+
+    /// authority is the authority_check key
+    #[allow(clippy::too_many_arguments)]
+    fn check_deposit(
+        &self,
+        ix: InstructionKind,
+        authority: &Pubkey,
+        pending_deposit: Option<&PendingDeposit>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        deposit_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_phase(ix)?;
+        self.check_transfer_window(ix, unix_timestamp)?;
+        self.check_whitelist(authority)?;
+        self.check_user_mints(user_vault_tokens, user_ul_tokens)?;
+
+        if deposit_amount == 0 {
+            return Err(ErrorCode::InvalidDepositAmount);
+        }
+        if deposit_amount > user_ul_tokens.amount {
+            return Err(ErrorCode::InsufficientCollateralForDeposit);
+        }
+
+        // deposit_with_claim claims a pending deposit from an older round first
+        if let Some(pd) = pending_deposit {
+            if ix == InstructionKind::Deposit
+                && pd.round_number != 0
+                && pd.round_number != self.volt_vault.round_number
+                && pd.num_underlying_deposited > 0
+            {
+                return Err(ErrorCode::CantHaveMultiplePendingDeposits);
+            }
+        }
+
+        let vault_total = self
+            .deposit_pool
+            .amount
+            .checked_add(self.round.underlying_from_pending_deposits)
+            .and_then(|total| total.checked_add(deposit_amount))
+            .ok_or(ErrorCode::NumberOverflow)?;
+        if self.volt_vault.capacity > 0 && vault_total > self.volt_vault.capacity {
+            return Err(ErrorCode::VaultCapacityWouldBeExceeded);
+        }
+
+        let user_total = self
+            .current_round_pending_deposit(pending_deposit)
+            .checked_add(deposit_amount)
+            .ok_or(ErrorCode::NumberOverflow)?;
+        if self.volt_vault.individual_capacity > 0
+            && user_total > self.volt_vault.individual_capacity
+        {
+            return Err(ErrorCode::IndividualDepositCapacityWouldBeExceeded);
+        }
+
+        Ok(())
+    }
+
+    pub fn deposit(
+        &self,
+        authority: &Pubkey,
+        pending_deposit: Option<&PendingDeposit>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        deposit_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_deposit(
+            InstructionKind::Deposit,
+            authority,
+            pending_deposit,
+            user_vault_tokens,
+            user_ul_tokens,
+            deposit_amount,
+            unix_timestamp,
+        )
+    }
+
+    pub fn deposit_with_claim(
+        &self,
+        authority: &Pubkey,
+        pending_deposit: Option<&PendingDeposit>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        deposit_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_deposit(
+            InstructionKind::DepositWithClaim,
+            authority,
+            pending_deposit,
+            user_vault_tokens,
+            user_ul_tokens,
+            deposit_amount,
+            unix_timestamp,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn check_withdraw(
+        &self,
+        ix: InstructionKind,
+        authority: &Pubkey,
+        pending_withdrawal: Option<&PendingWithdrawal>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        withdraw_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_phase(ix)?;
+        self.check_transfer_window(ix, unix_timestamp)?;
+        self.check_whitelist(authority)?;
+        self.check_user_mints(user_vault_tokens, user_ul_tokens)?;
+
+        if withdraw_amount > user_vault_tokens.amount {
+            return Err(ErrorCode::InsufficientVaultTokens);
+        }
+
+        if let Some(pw) = pending_withdrawal {
+            if ix == InstructionKind::Withdraw
+                && pw.round_number != 0
+                && pw.round_number != self.volt_vault.round_number
+                && pw.num_volt_redeemed > 0
+            {
+                return Err(ErrorCode::CantHaveMultiplePendingWithdrawals);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw(
+        &self,
+        authority: &Pubkey,
+        pending_withdrawal: Option<&PendingWithdrawal>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        withdraw_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_withdraw(
+            InstructionKind::Withdraw,
+            authority,
+            pending_withdrawal,
+            user_vault_tokens,
+            user_ul_tokens,
+            withdraw_amount,
+            unix_timestamp,
+        )
+    }
+
+    pub fn withdraw_with_claim(
+        &self,
+        authority: &Pubkey,
+        pending_withdrawal: Option<&PendingWithdrawal>,
+        user_vault_tokens: &SplTokenAccount,
+        user_ul_tokens: &SplTokenAccount,
+        withdraw_amount: u64,
+        unix_timestamp: i64,
+    ) -> PreflightResult {
+        self.check_withdraw(
+            InstructionKind::WithdrawWithClaim,
+            authority,
+            pending_withdrawal,
+            user_vault_tokens,
+            user_ul_tokens,
+            withdraw_amount,
+            unix_timestamp,
+        )
+    }
+
+    // pending_deposit is None if the PDA does not exist
+    pub fn claim_pending_deposit(
+        &self,
+        pending_deposit: Option<&PendingDeposit>,
+    ) -> PreflightResult {
+        check_claim_pending_deposit(self.volt_vault, self.extra_volt_data, pending_deposit)
+    }
+
+    pub fn claim_pending_withdrawal(
+        &self,
+        pending_withdrawal: Option<&PendingWithdrawal>,
+    ) -> PreflightResult {
+        check_claim_pending_withdrawal(self.volt_vault, self.extra_volt_data, pending_withdrawal)
+    }
+
+    pub fn cancel_pending_deposit(
+        &self,
+        pending_deposit: Option<&PendingDeposit>,
+    ) -> PreflightResult {
+        check_cancel_pending_deposit(self.volt_vault, self.extra_volt_data, pending_deposit)
+    }
+
+    pub fn cancel_pending_withdrawal(
+        &self,
+        pending_withdrawal: Option<&PendingWithdrawal>,
+    ) -> PreflightResult {
+        check_cancel_pending_withdrawal(self.volt_vault, self.extra_volt_data, pending_withdrawal)
+    }
+}
+
+// claims and cancels only read the volt and the pending deposit/withdrawal, so they don't need a PreflightVolt
+
+pub(crate) fn check_claim_pending_deposit(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    pending_deposit: Option<&PendingDeposit>,
+) -> PreflightResult {
+    check_phase(
+        volt_vault,
+        extra_volt_data,
+        InstructionKind::ClaimPendingDeposit,
+    )?;
+    let pd = pending_deposit
+        .filter(|pd| pd.initialized)
+        .ok_or(ErrorCode::PendingDepositInfoNotInitialized)?;
+    if pd.round_number == 0 || pd.num_underlying_deposited == 0 {
+        return Err(ErrorCode::PendingDepositDoesNotExist);
+    }
+    if pd.round_number >= volt_vault.round_number {
+        return Err(ErrorCode::InvalidClaimPendingState);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_claim_pending_withdrawal(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    pending_withdrawal: Option<&PendingWithdrawal>,
+) -> PreflightResult {
+    check_phase(
+        volt_vault,
+        extra_volt_data,
+        InstructionKind::ClaimPendingWithdrawal,
+    )?;
+    let pw = pending_withdrawal
+        .filter(|pw| pw.initialized)
+        .ok_or(ErrorCode::PendingWithdrawalInfoNotInitialized)?;
+    if pw.round_number == 0 || pw.num_volt_redeemed == 0 {
+        return Err(ErrorCode::PendingWithdrawalDoesNotExist);
+    }
+    if pw.round_number >= volt_vault.round_number {
+        return Err(ErrorCode::InvalidClaimPendingWithdrawalState);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_cancel_pending_deposit(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    pending_deposit: Option<&PendingDeposit>,
+) -> PreflightResult {
+    check_phase(
+        volt_vault,
+        extra_volt_data,
+        InstructionKind::CancelPendingDeposit,
+    )?;
+    let pd = pending_deposit
+        .filter(|pd| pd.initialized)
+        .ok_or(ErrorCode::PendingDepositInfoNotInitialized)?;
+    if pd.round_number == 0 || pd.num_underlying_deposited == 0 {
+        return Err(ErrorCode::PendingDepositDoesNotExist);
+    }
+    if pd.round_number != volt_vault.round_number {
+        return Err(ErrorCode::CannotCancelPendingDepositFromOldRound);
+    }
+    Ok(())
+}
+
+pub(crate) fn check_cancel_pending_withdrawal(
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    pending_withdrawal: Option<&PendingWithdrawal>,
+) -> PreflightResult {
+    check_phase(
+        volt_vault,
+        extra_volt_data,
+        InstructionKind::CancelPendingWithdrawal,
+    )?;
+    let pw = pending_withdrawal
+        .filter(|pw| pw.initialized)
+        .ok_or(ErrorCode::PendingWithdrawalInfoNotInitialized)?;
+    if pw.round_number == 0 || pw.num_volt_redeemed == 0 {
+        return Err(ErrorCode::PendingWithdrawalDoesNotExist);
+    }
+    if pw.round_number != volt_vault.round_number {
+        return Err(ErrorCode::CannotCancelPendingWithdrawalFromOldRound);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: u64 = 1_000;
+    const WINDOW: u64 = 100;
+
+    fn volt_vault() -> VoltVault {
+        VoltVault {
+            initialized: true,
+            round_has_started: true,
+            round_number: 3,
+            vault_mint: Pubkey::new_unique(),
+            start_transfer_time: START,
+            transfer_window: WINDOW,
+            ..VoltVault::default()
+        }
+    }
+
+    fn token_account(mint: Pubkey, amount: u64) -> SplTokenAccount {
+        SplTokenAccount {
+            mint,
+            amount,
+            ..SplTokenAccount::default()
+        }
+    }
+
+    struct Fixture {
+        volt_vault: VoltVault,
+        extra_volt_data: ExtraVoltData,
+        round: Round,
+        deposit_pool: SplTokenAccount,
+        whitelist: Option<Whitelist>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            Fixture {
+                volt_vault: volt_vault(),
+                extra_volt_data: ExtraVoltData::default(),
+                round: Round::default(),
+                deposit_pool: token_account(Pubkey::new_unique(), 0),
+                whitelist: None,
+            }
+        }
+
+        fn preflight(&self) -> PreflightVolt<'_> {
+            PreflightVolt {
+                volt_vault: &self.volt_vault,
+                extra_volt_data: &self.extra_volt_data,
+                round: &self.round,
+                deposit_pool: &self.deposit_pool,
+                whitelist: self.whitelist.as_ref(),
+            }
+        }
+
+        fn user_vault_tokens(&self, amount: u64) -> SplTokenAccount {
+            token_account(self.volt_vault.vault_mint, amount)
+        }
+
+        fn user_ul_tokens(&self, amount: u64) -> SplTokenAccount {
+            token_account(self.deposit_pool.mint, amount)
+        }
+
+        fn deposit(
+            &self,
+            pending_deposit: Option<&PendingDeposit>,
+            amount: u64,
+        ) -> PreflightResult {
+            self.deposit_at(pending_deposit, amount, START as i64)
+        }
+
+        fn deposit_at(
+            &self,
+            pending_deposit: Option<&PendingDeposit>,
+            amount: u64,
+            unix_timestamp: i64,
+        ) -> PreflightResult {
+            self.preflight().deposit(
+                &Pubkey::default(),
+                pending_deposit,
+                &self.user_vault_tokens(0),
+                &self.user_ul_tokens(1_000),
+                amount,
+                unix_timestamp,
+            )
+        }
+    }
+
+    fn pending_deposit(round_number: u64, amount: u64) -> PendingDeposit {
+        PendingDeposit {
+            initialized: true,
+            round_number,
+            num_underlying_deposited: amount,
+        }
+    }
+
+    fn pending_withdrawal(round_number: u64, amount: u64) -> PendingWithdrawal {
+        PendingWithdrawal {
+            initialized: true,
+            round_number,
+            num_volt_redeemed: amount,
+        }
+    }
+
+    #[test]
+    fn transfer_window() {
+        let fixture = Fixture::new();
+        assert!(fixture.deposit(None, 1).is_ok());
+        assert!(fixture.deposit_at(None, 1, (START + WINDOW) as i64).is_ok());
+        assert!(matches!(
+            fixture.deposit_at(None, 1, START as i64 - 1),
+            Err(ErrorCode::InvalidDepositTime)
+        ));
+        assert!(matches!(
+            fixture.deposit_at(None, 1, (START + WINDOW + 1) as i64),
+            Err(ErrorCode::InvalidDepositTime)
+        ));
+        assert!(matches!(
+            fixture.deposit_at(None, 1, -1),
+            Err(ErrorCode::InvalidDepositTime)
+        ));
+        assert!(matches!(
+            check_transfer_window(
+                &fixture.volt_vault,
+                &fixture.extra_volt_data,
+                InstructionKind::Withdraw,
+                i64::MIN
+            ),
+            Err(ErrorCode::InvalidWithdrawalTime)
+        ));
+
+        // end_transfer_time extends the window
+        let mut fixture = Fixture::new();
+        fixture.volt_vault.end_transfer_time = START + 2 * WINDOW;
+        assert!(fixture
+            .deposit_at(None, 1, (START + 2 * WINDOW) as i64)
+            .is_ok());
+        assert!(matches!(
+            fixture.deposit_at(None, 1, (START + 2 * WINDOW + 1) as i64),
+            Err(ErrorCode::InvalidDepositTime)
+        ));
+
+        // no window length set, open ended
+        fixture.volt_vault.transfer_window = 0;
+        fixture.volt_vault.end_transfer_time = 0;
+        assert!(fixture.deposit_at(None, 1, i64::MAX).is_ok());
+
+        fixture.extra_volt_data.turn_off_deposits_and_withdrawals = true;
+        assert!(matches!(
+            fixture.deposit(None, 1),
+            Err(ErrorCode::DepositsAndWithdrawalsAreTurnedOff)
+        ));
+    }
+
+    #[test]
+    fn round_phase() {
+        let mut fixture = Fixture::new();
+        fixture.volt_vault.round_has_started = false;
+        assert!(matches!(
+            fixture.deposit(None, 1),
+            Err(ErrorCode::RoundHasNotStarted)
+        ));
+    }
+
+    #[test]
+    fn capacity() {
+        let mut fixture = Fixture::new();
+        fixture.deposit_pool.amount = 500;
+        fixture.round.underlying_from_pending_deposits = 300;
+        fixture.volt_vault.capacity = 1_000;
+        assert!(fixture.deposit(None, 200).is_ok());
+        assert!(matches!(
+            fixture.deposit(None, 201),
+            Err(ErrorCode::VaultCapacityWouldBeExceeded)
+        ));
+
+        fixture.volt_vault.capacity = 0;
+        fixture.volt_vault.individual_capacity = 100;
+        let pd = pending_deposit(fixture.volt_vault.round_number, 60);
+        assert!(fixture.deposit(Some(&pd), 40).is_ok());
+        assert!(matches!(
+            fixture.deposit(Some(&pd), 41),
+            Err(ErrorCode::IndividualDepositCapacityWouldBeExceeded)
+        ));
+
+        fixture.deposit_pool.amount = u64::MAX;
+        assert!(matches!(
+            fixture.deposit(None, 1),
+            Err(ErrorCode::NumberOverflow)
+        ));
+    }
+
+    #[test]
+    fn deposit_amount() {
+        let fixture = Fixture::new();
+        assert!(matches!(
+            fixture.deposit(None, 0),
+            Err(ErrorCode::InvalidDepositAmount)
+        ));
+        assert!(matches!(
+            fixture.deposit(None, 1_001),
+            Err(ErrorCode::InsufficientCollateralForDeposit)
+        ));
+    }
+
+    #[test]
+    fn whitelist() {
+        let user = Pubkey::new_unique();
+        let mut fixture = Fixture::new();
+        fixture.extra_volt_data.is_whitelisted = true;
+        assert!(matches!(
+            fixture.preflight().check_whitelist(&user),
+            Err(ErrorCode::InvalidWhitelistAccountVector)
+        ));
+
+        fixture.whitelist = Some(Whitelist::default());
+        assert!(matches!(
+            fixture.preflight().check_whitelist(&user),
+            Err(ErrorCode::NonWhitelistedUser)
+        ));
+
+        fixture.whitelist.as_mut().unwrap().addresses.push(user);
+        assert!(fixture.preflight().check_whitelist(&user).is_ok());
+    }
+
+    #[test]
+    fn mints() {
+        let fixture = Fixture::new();
+        let preflight = fixture.preflight();
+        let wrong_mint = token_account(Pubkey::new_unique(), 1_000);
+        let args = |user_vault_tokens: &SplTokenAccount, user_ul_tokens: &SplTokenAccount| {
+            preflight.deposit(
+                &Pubkey::default(),
+                None,
+                user_vault_tokens,
+                user_ul_tokens,
+                1,
+                START as i64,
+            )
+        };
+        assert!(matches!(
+            args(&wrong_mint, &fixture.user_ul_tokens(1_000)),
+            Err(ErrorCode::VaultMintDoesNotMatchUserTokenAccount)
+        ));
+        assert!(matches!(
+            args(&fixture.user_vault_tokens(0), &wrong_mint),
+            Err(ErrorCode::DepositPoolMintDoesNotMatchUserTokenAccount)
+        ));
+    }
+
+    #[test]
+    fn pending_from_older_round() {
+        let fixture = Fixture::new();
+        let old_pd = pending_deposit(fixture.volt_vault.round_number - 1, 10);
+        assert!(matches!(
+            fixture.deposit(Some(&old_pd), 1),
+            Err(ErrorCode::CantHaveMultiplePendingDeposits)
+        ));
+        assert!(fixture
+            .preflight()
+            .deposit_with_claim(
+                &Pubkey::default(),
+                Some(&old_pd),
+                &fixture.user_vault_tokens(0),
+                &fixture.user_ul_tokens(1_000),
+                1,
+                START as i64,
+            )
+            .is_ok());
+
+        let old_pw = pending_withdrawal(fixture.volt_vault.round_number - 1, 10);
+        let withdraw = |pw: Option<&PendingWithdrawal>, amount: u64| {
+            fixture.preflight().withdraw(
+                &Pubkey::default(),
+                pw,
+                &fixture.user_vault_tokens(100),
+                &fixture.user_ul_tokens(0),
+                amount,
+                START as i64,
+            )
+        };
+        assert!(withdraw(None, 100).is_ok());
+        assert!(matches!(
+            withdraw(None, 101),
+            Err(ErrorCode::InsufficientVaultTokens)
+        ));
+        assert!(matches!(
+            withdraw(Some(&old_pw), 1),
+            Err(ErrorCode::CantHaveMultiplePendingWithdrawals)
+        ));
+    }
+
+    #[test]
+    fn pending_claim_state() {
+        let fixture = Fixture::new();
+        let preflight = fixture.preflight();
+        let round_number = fixture.volt_vault.round_number;
+
+        assert!(matches!(
+            preflight.claim_pending_deposit(None),
+            Err(ErrorCode::PendingDepositInfoNotInitialized)
+        ));
+        assert!(matches!(
+            preflight.claim_pending_deposit(Some(&pending_deposit(0, 0))),
+            Err(ErrorCode::PendingDepositDoesNotExist)
+        ));
+        assert!(matches!(
+            preflight.claim_pending_deposit(Some(&pending_deposit(round_number, 10))),
+            Err(ErrorCode::InvalidClaimPendingState)
+        ));
+        assert!(preflight
+            .claim_pending_deposit(Some(&pending_deposit(round_number - 1, 10)))
+            .is_ok());
+
+        assert!(matches!(
+            preflight.claim_pending_withdrawal(None),
+            Err(ErrorCode::PendingWithdrawalInfoNotInitialized)
+        ));
+        assert!(matches!(
+            preflight.claim_pending_withdrawal(Some(&pending_withdrawal(round_number, 0))),
+            Err(ErrorCode::PendingWithdrawalDoesNotExist)
+        ));
+        assert!(matches!(
+            preflight.claim_pending_withdrawal(Some(&pending_withdrawal(round_number, 10))),
+            Err(ErrorCode::InvalidClaimPendingWithdrawalState)
+        ));
+        assert!(preflight
+            .claim_pending_withdrawal(Some(&pending_withdrawal(round_number - 1, 10)))
+            .is_ok());
+
+        assert!(matches!(
+            preflight.cancel_pending_deposit(Some(&pending_deposit(round_number - 1, 10))),
+            Err(ErrorCode::CannotCancelPendingDepositFromOldRound)
+        ));
+        assert!(preflight
+            .cancel_pending_deposit(Some(&pending_deposit(round_number, 10)))
+            .is_ok());
+        assert!(matches!(
+            preflight.cancel_pending_withdrawal(Some(&pending_withdrawal(round_number - 1, 10))),
+            Err(ErrorCode::CannotCancelPendingWithdrawalFromOldRound)
+        ));
+        assert!(preflight
+            .cancel_pending_withdrawal(Some(&pending_withdrawal(round_number, 10)))
+            .is_ok());
+    }
+}
@@ -0,0 +1,390 @@
+use std::str::FromStr;
+
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/**
+ * Error a volt program invocation failed with, as reported in transaction logs.
+ */
+#[derive(Clone, Debug)]
+pub enum VoltProgramError {
+    // error from error.rs
+    Volt(ErrorCode),
+    // anchor framework error, e.g. ConstraintSeeds or AccountNotInitialized
+    Anchor(anchor_lang::error::ErrorCode),
+    // custom program error that matches neither of the above
+    Custom(u32),
+    // failure that is not a custom program error, e.g. running out of compute units
+    Runtime(String),
+}
+
+/**
+ * A failed volt program invocation, parsed from transaction log messages.
+ *  NOTES:
+ *  1. account_name, error_message and compared_values are only known if the program logged an AnchorError
+ *  2. compared_values is (left, right) as logged by anchor for ConstraintSeeds, ConstraintAddress and require_*! checks
+ */
+#[derive(Clone, Debug)]
+pub struct VoltProgramFailure {
+    pub error: VoltProgramError,
+    pub account_name: Option<String>,
+    pub error_message: Option<String>,
+    pub compared_values: Option<(String, String)>,
+
+    // 1 if the volt program was invoked by the transaction directly, > 1 if it was invoked via CPI
+    pub depth: u32,
+
+    pub compute_units_consumed: Option<u64>,
+}
+
+const ANCHOR_ERROR_CODES: &[anchor_lang::error::ErrorCode] = {
+    use anchor_lang::error::ErrorCode::*;
+    &[
+        InstructionMissing,
+        InstructionFallbackNotFound,
+        InstructionDidNotDeserialize,
+        InstructionDidNotSerialize,
+        IdlInstructionStub,
+        IdlInstructionInvalidProgram,
+        ConstraintMut,
+        ConstraintHasOne,
+        ConstraintSigner,
+        ConstraintRaw,
+        ConstraintOwner,
+        ConstraintRentExempt,
+        ConstraintSeeds,
+        ConstraintExecutable,
+        ConstraintState,
+        ConstraintAssociated,
+        ConstraintAssociatedInit,
+        ConstraintClose,
+        ConstraintAddress,
+        ConstraintZero,
+        ConstraintTokenMint,
+        ConstraintTokenOwner,
+        ConstraintMintMintAuthority,
+        ConstraintMintFreezeAuthority,
+        ConstraintMintDecimals,
+        ConstraintSpace,
+        RequireViolated,
+        RequireEqViolated,
+        RequireKeysEqViolated,
+        RequireNeqViolated,
+        RequireKeysNeqViolated,
+        RequireGtViolated,
+        RequireGteViolated,
+        AccountDiscriminatorAlreadySet,
+        AccountDiscriminatorNotFound,
+        AccountDiscriminatorMismatch,
+        AccountDidNotDeserialize,
+        AccountDidNotSerialize,
+        AccountNotEnoughKeys,
+        AccountNotMutable,
+        AccountOwnedByWrongProgram,
+        InvalidProgramId,
+        InvalidProgramExecutable,
+        AccountNotSigner,
+        AccountNotSystemOwned,
+        AccountNotInitialized,
+        AccountNotProgramData,
+        AccountNotAssociatedTokenAccount,
+        AccountSysvarMismatch,
+        AccountReallocExceedsLimit,
+        AccountDuplicateReallocs,
+        StateInvalidAddress,
+        DeclaredProgramIdMismatch,
+        Deprecated,
+    ]
+};
+
+impl VoltProgramError {
+    /// maps an error number to the volt or anchor error it belongs to
+    pub fn from_error_number(error_number: u32) -> Self {
+        if let Some(error_code) = ErrorCode::from_code(error_number) {
+            return VoltProgramError::Volt(error_code);
+        }
+        if let Some(error_code) = ANCHOR_ERROR_CODES
+            .iter()
+            .find(|e| u32::from(**e) == error_number)
+        {
+            return VoltProgramError::Anchor(*error_code);
+        }
+        VoltProgramError::Custom(error_number)
+    }
+
+    pub fn error_number(&self) -> Option<u32> {
+        match self {
+            VoltProgramError::Volt(error_code) => Some(u32::from(*error_code)),
+            VoltProgramError::Anchor(error_code) => Some(u32::from(*error_code)),
+            VoltProgramError::Custom(error_number) => Some(*error_number),
+            VoltProgramError::Runtime(_) => None,
+        }
+    }
+}
+
+// fields of an "AnchorError ..." log line
+struct AnchorErrorLog {
+    account_name: Option<String>,
+    error_number: Option<u32>,
+    error_message: Option<String>,
+}
+
+fn parse_anchor_error_log(log: &str) -> Option<AnchorErrorLog> {
+    let log = log.strip_prefix("AnchorError ")?;
+    let account_name = log
+        .strip_prefix("caused by account: ")
+        .and_then(|rest| rest.split(". Error Code: ").next())
+        .map(String::from);
+    let error_number = log
+        .split("Error Number: ")
+        .nth(1)
+        .and_then(|rest| rest.split('.').next())
+        .and_then(|n| n.parse().ok());
+    let error_message = log
+        .split("Error Message: ")
+        .nth(1)
+        .map(|msg| msg.strip_suffix('.').unwrap_or(msg).to_string());
+    Some(AnchorErrorLog {
+        account_name,
+        error_number,
+        error_message,
+    })
+}
+
+// "Program <program_id> <rest>"
+fn parse_program_line(line: &str) -> Option<(Pubkey, &str)> {
+    let line = line.strip_prefix("Program ")?;
+    let (program_id, rest) = line.split_once(' ')?;
+    Some((Pubkey::from_str(program_id).ok()?, rest))
+}
+
+/// finds the first failed invocation of the volt program (crate::id() if program_id is None) in a
+/// transaction's log messages. returns None if the volt program did not fail
+pub fn parse_volt_program_failure(
+    log_messages: &[String],
+    program_id: Option<&Pubkey>,
+) -> Option<VoltProgramFailure> {
+    let program_id = *program_id.unwrap_or(&crate::id());
+
+    // program ids of the invocations that have not returned yet
+    let mut invoke_stack: Vec<Pubkey> = Vec::new();
+    let mut anchor_error: Option<AnchorErrorLog> = None;
+    let mut left: Option<String> = None;
+    let mut right: Option<String> = None;
+    let mut compute_units_consumed: Option<u64> = None;
+
+    let mut lines = log_messages.iter().map(String::as_str);
+    while let Some(line) = lines.next() {
+        let in_volt_program = invoke_stack.last() == Some(&program_id);
+
+        if let Some(log) = line.strip_prefix("Program log: ") {
+            if !in_volt_program {
+                continue;
+            }
+            if let Some(parsed) = parse_anchor_error_log(log) {
+                anchor_error = Some(parsed);
+            } else if log == "Left:" {
+                left = lines
+                    .next()
+                    .map(|l| l.strip_prefix("Program log: ").unwrap_or(l).to_string());
+            } else if log == "Right:" {
+                right = lines
+                    .next()
+                    .map(|l| l.strip_prefix("Program log: ").unwrap_or(l).to_string());
+            } else if let Some(value) = log.strip_prefix("Left: ") {
+                left = Some(value.to_string());
+            } else if let Some(value) = log.strip_prefix("Right: ") {
+                right = Some(value.to_string());
+            }
+            continue;
+        }
+
+        let (invoked_program_id, rest) = match parse_program_line(line) {
+            Some(parsed) => parsed,
+            None => continue,
+        };
+
+        if rest.starts_with("invoke [") {
+            invoke_stack.push(invoked_program_id);
+            if invoked_program_id == program_id {
+                anchor_error = None;
+                left = None;
+                right = None;
+                compute_units_consumed = None;
+            }
+        } else if rest == "success" {
+            invoke_stack.pop();
+        } else if let Some(consumed) = rest.strip_prefix("consumed ") {
+            if invoked_program_id == program_id {
+                compute_units_consumed = consumed.split(' ').next().and_then(|n| n.parse().ok());
+            }
+        } else if let Some(reason) = rest.strip_prefix("failed: ") {
+            if invoked_program_id != program_id {
+                invoke_stack.pop();
+                continue;
+            }
+
+            let error = match reason.strip_prefix("custom program error: 0x") {
+                Some(hex) => match u32::from_str_radix(hex.trim(), 16) {
+                    Ok(error_number) => VoltProgramError::from_error_number(error_number),
+                    Err(_) => VoltProgramError::Runtime(reason.to_string()),
+                },
+                None => match anchor_error.as_ref().and_then(|e| e.error_number) {
+                    Some(error_number) => VoltProgramError::from_error_number(error_number),
+                    None => VoltProgramError::Runtime(reason.to_string()),
+                },
+            };
+            let (account_name, error_message) = match anchor_error {
+                Some(anchor_error) => (anchor_error.account_name, anchor_error.error_message),
+                None => (None, None),
+            };
+
+            return Some(VoltProgramFailure {
+                error,
+                account_name,
+                error_message,
+                compared_values: left.zip(right),
+                depth: invoke_stack.len() as u32,
+                compute_units_consumed,
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(lines: &[&str]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.replace("VOLT", &crate::id().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn anchor_constraint_error() {
+        let failure = parse_volt_program_failure(
+            &logs(&[
+                "Program VOLT invoke [1]",
+                "Program log: Instruction: Deposit",
+                "Program log: AnchorError caused by account: round_info. Error Code: ConstraintSeeds. Error Number: 2006. Error Message: A seeds constraint was violated.",
+                "Program log: Left:",
+                "Program log: 11111111111111111111111111111111",
+                "Program log: Right:",
+                "Program log: So11111111111111111111111111111111111111112",
+                "Program VOLT consumed 21034 of 200000 compute units",
+                "Program VOLT failed: custom program error: 0x7d6",
+            ]),
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            failure.error,
+            VoltProgramError::Anchor(anchor_lang::error::ErrorCode::ConstraintSeeds)
+        ));
+        assert_eq!(failure.account_name.as_deref(), Some("round_info"));
+        assert_eq!(
+            failure.error_message.as_deref(),
+            Some("A seeds constraint was violated")
+        );
+        assert_eq!(
+            failure.compared_values,
+            Some((
+                "11111111111111111111111111111111".to_string(),
+                "So11111111111111111111111111111111111111112".to_string()
+            ))
+        );
+        assert_eq!(failure.depth, 1);
+        assert_eq!(failure.compute_units_consumed, Some(21034));
+    }
+
+    #[test]
+    fn volt_error_via_cpi() {
+        let error_number = u32::from(ErrorCode::VaultCapacityWouldBeExceeded);
+        let failed = format!(
+            "Program VOLT failed: custom program error: {:#x}",
+            error_number
+        );
+        let caller = Pubkey::new_unique().to_string();
+        let failure = parse_volt_program_failure(
+            &logs(&[
+                &format!("Program {} invoke [1]", caller),
+                "Program VOLT invoke [2]",
+                "Program log: Instruction: Deposit",
+                "Program VOLT consumed 5000 of 180000 compute units",
+                &failed,
+                &format!("Program {} consumed 25000 of 200000 compute units", caller),
+                &format!(
+                    "Program {} failed: custom program error: {:#x}",
+                    caller, error_number
+                ),
+            ]),
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            failure.error,
+            VoltProgramError::Volt(ErrorCode::VaultCapacityWouldBeExceeded)
+        ));
+        assert_eq!(failure.error.error_number(), Some(error_number));
+        assert_eq!(failure.account_name, None);
+        assert_eq!(failure.depth, 2);
+        assert_eq!(failure.compute_units_consumed, Some(5000));
+    }
+
+    #[test]
+    fn runtime_failure() {
+        let failure = parse_volt_program_failure(
+            &logs(&[
+                "Program VOLT invoke [1]",
+                "Program VOLT consumed 200000 of 200000 compute units",
+                "Program VOLT failed: exceeded CUs meter at BPF instruction #1234",
+            ]),
+            None,
+        )
+        .unwrap();
+        assert!(matches!(
+            failure.error,
+            VoltProgramError::Runtime(ref reason) if reason.starts_with("exceeded CUs meter")
+        ));
+        assert_eq!(failure.error.error_number(), None);
+    }
+
+    #[test]
+    fn ignores_other_programs() {
+        let other = Pubkey::new_unique();
+        let lines = logs(&[
+            "Program VOLT invoke [1]",
+            "Program VOLT consumed 1000 of 200000 compute units",
+            "Program VOLT success",
+            &format!("Program {} invoke [1]", other),
+            &format!("Program {} failed: custom program error: 0x1", other),
+        ]);
+        assert!(parse_volt_program_failure(&lines, None).is_none());
+
+        let failure = parse_volt_program_failure(&lines, Some(&other)).unwrap();
+        assert!(matches!(failure.error, VoltProgramError::Custom(1)));
+    }
+
+    #[test]
+    fn error_numbers() {
+        assert!(matches!(
+            VoltProgramError::from_error_number(3012),
+            VoltProgramError::Anchor(anchor_lang::error::ErrorCode::AccountNotInitialized)
+        ));
+        assert!(matches!(
+            VoltProgramError::from_error_number(u32::from(ErrorCode::InvalidDepositAmount)),
+            VoltProgramError::Volt(ErrorCode::InvalidDepositAmount)
+        ));
+        assert!(matches!(
+            VoltProgramError::from_error_number(1),
+            VoltProgramError::Custom(1)
+        ));
+    }
+}
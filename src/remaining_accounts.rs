@@ -0,0 +1,379 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::AccountMeta;
+
+use crate::error::{AbiErrorCode, ErrorCode};
+use crate::pda::{find_extra_volt_data_address, find_protection_vault_address};
+use crate::{
+    EntropyBaseAccountsWithoutBanks, ExtraVoltData, PrincipalProtectionContextAccounts,
+    PrincipalProtectionVaultV1, VoltType, VoltVault,
+};
+
+/**
+ * Volt-specific accounts passed as remaining_accounts to deposit and deposit_with_claim.
+ *  NOTES:
+ *  1. entropy volts take the accounts in EntropyBaseAccountsWithoutBanks
+ *  2. principal protection volts take the accounts in PrincipalProtectionContextAccounts
+ *  3. short options volts take no remaining accounts. volt types unknown to this crate are rejected with InvalidVoltType,
+ *     rather than sending a deposit w/o the accounts the program expects
+ *  4. program_id is the volt program (None for the mainnet program id), PDAs are derived from it.
+ *     owners of typed accounts are still checked against crate::ID by anchor
+ */
+pub enum VoltRemainingAccounts<'info> {
+    None,
+    Entropy(Box<EntropyBaseAccountsWithoutBanks<'info>>),
+    PrincipalProtection(Box<PrincipalProtectionContextAccounts<'info>>),
+}
+
+impl<'info> VoltRemainingAccounts<'info> {
+    // parses (and advances past) the accounts appended by deposit_remaining_account_metas, in the same order
+    pub fn from_remaining_accounts(
+        volt_key: &Pubkey,
+        volt_vault: &VoltVault,
+        accounts: &mut &[AccountInfo<'info>],
+        program_id: Option<&Pubkey>,
+    ) -> Result<Self> {
+        let program_id = program_id.copied().unwrap_or_else(crate::id);
+        match volt_vault.volt_type() {
+            VoltType::Entropy => {
+                let entropy_accounts = EntropyBaseAccountsWithoutBanks::try_accounts(
+                    &program_id,
+                    accounts,
+                    &[],
+                    &mut BTreeMap::new(),
+                    &mut BTreeSet::new(),
+                )?;
+                require_keys_eq!(
+                    entropy_accounts.extra_volt_data.key(),
+                    find_extra_volt_data_address(volt_key, Some(&program_id)).0,
+                    anchor_lang::error::ErrorCode::ConstraintSeeds
+                );
+                Ok(VoltRemainingAccounts::Entropy(Box::new(entropy_accounts)))
+            }
+            VoltType::PrincipalProtection => {
+                let pp_accounts = PrincipalProtectionContextAccounts::try_accounts(
+                    &program_id,
+                    accounts,
+                    &[],
+                    &mut BTreeMap::new(),
+                    &mut BTreeSet::new(),
+                )?;
+                require_keys_eq!(
+                    pp_accounts.volt_vault.key(),
+                    *volt_key,
+                    anchor_lang::error::ErrorCode::ConstraintAddress
+                );
+                Ok(VoltRemainingAccounts::PrincipalProtection(Box::new(
+                    pp_accounts,
+                )))
+            }
+            VoltType::ShortOptions => Ok(VoltRemainingAccounts::None),
+            VoltType::Unknown(_) => err!(ErrorCode::InvalidVoltType),
+        }
+    }
+
+    // account infos in the order from_remaining_accounts parses them
+    pub fn to_account_infos(&self) -> Vec<AccountInfo<'info>> {
+        match self {
+            VoltRemainingAccounts::None => vec![],
+            VoltRemainingAccounts::Entropy(entropy_accounts) => entropy_accounts.to_account_infos(),
+            VoltRemainingAccounts::PrincipalProtection(pp_accounts) => {
+                pp_accounts.to_account_infos()
+            }
+        }
+    }
+}
+
+/// client side EntropyBaseAccountsWithoutBanks, the remaining accounts of deposits into entropy volts
+pub fn entropy_base_client_accounts(
+    volt_key: &Pubkey,
+    extra_volt_data: &ExtraVoltData,
+    program_id: Option<&Pubkey>,
+) -> crate::__client_accounts_entropy_base_accounts_without_banks::EntropyBaseAccountsWithoutBanks {
+    crate::__client_accounts_entropy_base_accounts_without_banks::EntropyBaseAccountsWithoutBanks {
+        extra_volt_data: find_extra_volt_data_address(volt_key, program_id).0,
+        program: extra_volt_data.entropy_program_id,
+        group: extra_volt_data.entropy_group,
+        cache: extra_volt_data.entropy_cache,
+        account: extra_volt_data.entropy_account,
+    }
+}
+
+/// ordered remaining accounts to append to deposit and deposit_with_claim.
+/// pp_vault must be Some for principal protection volts. program_id is None for the mainnet program id
+pub fn deposit_remaining_account_metas(
+    volt_key: &Pubkey,
+    volt_vault: &VoltVault,
+    extra_volt_data: &ExtraVoltData,
+    pp_vault: Option<&PrincipalProtectionVaultV1>,
+    program_id: Option<&Pubkey>,
+) -> Result<Vec<AccountMeta>> {
+    match volt_vault.volt_type() {
+        VoltType::Entropy => {
+            Ok(
+                entropy_base_client_accounts(volt_key, extra_volt_data, program_id)
+                    .to_account_metas(None),
+            )
+        }
+        VoltType::PrincipalProtection => {
+            let pp_vault = pp_vault
+                .ok_or_else(|| error!(AbiErrorCode::PrincipalProtectionVaultNotProvided))?;
+            Ok(
+                crate::__client_accounts_principal_protection_context_accounts::PrincipalProtectionContextAccounts {
+                    volt_vault: *volt_key,
+                    pp_vault: find_protection_vault_address(volt_key, program_id).0,
+                    deposit_tracking_account: pp_vault.get_deposit_tracking_account(),
+                    lending_shares_pool: pp_vault.get_lending_shares_pool(),
+                    lending_vault: pp_vault.get_primary_lending_vault_pk(),
+                    lending_vault_program: pp_vault.get_primary_lending_vault_program_id(),
+                }
+                .to_account_metas(None),
+            )
+        }
+        VoltType::ShortOptions => Ok(vec![]),
+        VoltType::Unknown(_) => err!(ErrorCode::InvalidVoltType),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+
+    fn entropy_volt() -> (VoltVault, ExtraVoltData) {
+        let volt_vault = VoltVault {
+            vault_type: VoltType::ENTROPY,
+            ..VoltVault::default()
+        };
+        let extra_volt_data = ExtraVoltData {
+            entropy_program_id: Pubkey::new_unique(),
+            entropy_group: Pubkey::new_unique(),
+            entropy_cache: Pubkey::new_unique(),
+            entropy_account: Pubkey::new_unique(),
+            ..ExtraVoltData::default()
+        };
+        (volt_vault, extra_volt_data)
+    }
+
+    #[test]
+    fn short_options_take_no_accounts() {
+        let volt_vault = VoltVault::default();
+        let metas = deposit_remaining_account_metas(
+            &Pubkey::new_unique(),
+            &volt_vault,
+            &ExtraVoltData::default(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(metas.is_empty());
+    }
+
+    #[test]
+    fn entropy_metas_in_order() {
+        let volt_key = Pubkey::new_unique();
+        let (volt_vault, extra_volt_data) = entropy_volt();
+        let program_id = Pubkey::new_unique();
+        let metas = deposit_remaining_account_metas(
+            &volt_key,
+            &volt_vault,
+            &extra_volt_data,
+            None,
+            Some(&program_id),
+        )
+        .unwrap();
+
+        let keys: Vec<Pubkey> = metas.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            vec![
+                find_extra_volt_data_address(&volt_key, Some(&program_id)).0,
+                extra_volt_data.entropy_program_id,
+                extra_volt_data.entropy_group,
+                extra_volt_data.entropy_cache,
+                extra_volt_data.entropy_account,
+            ]
+        );
+    }
+
+    fn principal_protection_volt() -> (VoltVault, PrincipalProtectionVaultV1) {
+        let volt_vault = VoltVault {
+            vault_type: VoltType::PRINCIPAL_PROTECTION,
+            ..VoltVault::default()
+        };
+        // borsh decodes zeroes as the first enum variants and an empty vault_name
+        let mut pp_vault =
+            <PrincipalProtectionVaultV1 as AnchorDeserialize>::deserialize(&mut &[0u8; 4096][..])
+                .unwrap();
+        pp_vault.keys.lending_shares_pool = Pubkey::new_unique();
+        let primary_vault = &mut pp_vault.keys.lending_keys.primary_vault;
+        primary_vault.deposit_tracking_account = Pubkey::new_unique();
+        primary_vault.vault = Pubkey::new_unique();
+        primary_vault.program_id = Pubkey::new_unique();
+        (volt_vault, pp_vault)
+    }
+
+    #[test]
+    fn principal_protection_requires_pp_vault() {
+        let (volt_vault, _) = principal_protection_volt();
+        assert_eq!(
+            deposit_remaining_account_metas(
+                &Pubkey::new_unique(),
+                &volt_vault,
+                &ExtraVoltData::default(),
+                None,
+                None,
+            )
+            .unwrap_err(),
+            error!(AbiErrorCode::PrincipalProtectionVaultNotProvided)
+        );
+    }
+
+    #[test]
+    fn unknown_volt_types_are_rejected() {
+        let volt_key = Pubkey::new_unique();
+        let volt_vault = VoltVault {
+            vault_type: 7,
+            ..VoltVault::default()
+        };
+        assert_eq!(
+            deposit_remaining_account_metas(
+                &volt_key,
+                &volt_vault,
+                &ExtraVoltData::default(),
+                None,
+                None,
+            )
+            .unwrap_err(),
+            error!(ErrorCode::InvalidVoltType)
+        );
+        let mut accounts: &[AccountInfo<'static>] = &[];
+        assert_eq!(
+            VoltRemainingAccounts::from_remaining_accounts(
+                &volt_key,
+                &volt_vault,
+                &mut accounts,
+                None
+            )
+            .err()
+            .unwrap(),
+            error!(ErrorCode::InvalidVoltType)
+        );
+    }
+
+    #[test]
+    fn parses_principal_protection_accounts_in_order() {
+        let volt_key = Pubkey::new_unique();
+        let (volt_vault, pp_vault) = principal_protection_volt();
+        let metas = deposit_remaining_account_metas(
+            &volt_key,
+            &volt_vault,
+            &ExtraVoltData::default(),
+            Some(&pp_vault),
+            None,
+        )
+        .unwrap();
+        let keys: Vec<Pubkey> = metas.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            vec![
+                volt_key,
+                find_protection_vault_address(&volt_key, None).0,
+                pp_vault.get_deposit_tracking_account(),
+                pp_vault.get_lending_shares_pool(),
+                pp_vault.get_primary_lending_vault_pk(),
+                pp_vault.get_primary_lending_vault_program_id(),
+            ]
+        );
+
+        let infos = [
+            program_account(keys[0], crate::ID, &volt_vault),
+            program_account(keys[1], crate::ID, &pp_vault),
+            account_info(keys[2], Pubkey::default(), vec![], false, true),
+            token_account(keys[3], Pubkey::new_unique(), Pubkey::new_unique(), 0),
+            account_info(keys[4], Pubkey::default(), vec![], false, true),
+            program_info(keys[5]),
+        ];
+        let mut accounts = &infos[..];
+        let parsed = VoltRemainingAccounts::from_remaining_accounts(
+            &volt_key,
+            &volt_vault,
+            &mut accounts,
+            None,
+        )
+        .unwrap();
+        assert!(accounts.is_empty());
+        assert!(matches!(
+            parsed,
+            VoltRemainingAccounts::PrincipalProtection(_)
+        ));
+        assert_eq!(
+            parsed
+                .to_account_infos()
+                .iter()
+                .map(|info| info.key())
+                .collect::<Vec<_>>(),
+            keys
+        );
+
+        // accounts of another volt
+        let mut accounts = &infos[..];
+        assert!(VoltRemainingAccounts::from_remaining_accounts(
+            &Pubkey::new_unique(),
+            &volt_vault,
+            &mut accounts,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parses_entropy_accounts_in_order() {
+        let volt_key = Pubkey::new_unique();
+        let (volt_vault, extra_volt_data) = entropy_volt();
+        let metas =
+            deposit_remaining_account_metas(&volt_key, &volt_vault, &extra_volt_data, None, None)
+                .unwrap();
+
+        let infos: Vec<AccountInfo<'static>> = std::iter::once(program_account(
+            metas[0].pubkey,
+            crate::ID,
+            &extra_volt_data,
+        ))
+        .chain(
+            metas[1..]
+                .iter()
+                .map(|meta| account_info(meta.pubkey, Pubkey::default(), vec![], false, false)),
+        )
+        .collect();
+
+        let mut accounts = &infos[..];
+        let parsed = VoltRemainingAccounts::from_remaining_accounts(
+            &volt_key,
+            &volt_vault,
+            &mut accounts,
+            None,
+        )
+        .unwrap();
+        assert!(accounts.is_empty());
+        assert_eq!(
+            parsed
+                .to_account_infos()
+                .iter()
+                .map(|info| info.key())
+                .collect::<Vec<_>>(),
+            infos.iter().map(|info| info.key()).collect::<Vec<_>>()
+        );
+
+        // extra_volt_data isn't the PDA of another program id
+        let mut accounts = &infos[..];
+        assert!(VoltRemainingAccounts::from_remaining_accounts(
+            &volt_key,
+            &volt_vault,
+            &mut accounts,
+            Some(&Pubkey::new_unique()),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,434 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::{ExtraVoltData, VoltVault};
+
+/**
+ * Every instruction of the volt program, without its arguments.
+ * Split into user instructions (callable by anyone holding the relevant accounts) and
+ * keeper instructions (permissioned, drive the round lifecycle).
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InstructionKind {
+    // user
+    Deposit,
+    Withdraw,
+    DepositWithClaim,
+    WithdrawWithClaim,
+    ClaimPendingDeposit,
+    ClaimPendingWithdrawal,
+    CancelPendingDeposit,
+    CancelPendingWithdrawal,
+
+    // keeper (short options)
+    StartRound,
+    SetNextOption,
+    RebalancePrepare,
+    RebalanceEnter,
+    RebalanceSwapPremium,
+    RebalanceSettle,
+    EndRound,
+
+    // keeper (entropy)
+    SettleDeposits,
+    SetupRebalance,
+    RebalanceEntropy,
+    ExitEarly,
+    TakePerformanceFees,
+    EndEntropyRound,
+}
+
+impl InstructionKind {
+    pub fn is_user_instruction(&self) -> bool {
+        matches!(
+            self,
+            InstructionKind::Deposit
+                | InstructionKind::Withdraw
+                | InstructionKind::DepositWithClaim
+                | InstructionKind::WithdrawWithClaim
+                | InstructionKind::ClaimPendingDeposit
+                | InstructionKind::ClaimPendingWithdrawal
+                | InstructionKind::CancelPendingDeposit
+                | InstructionKind::CancelPendingWithdrawal
+        )
+    }
+
+    pub fn is_keeper_instruction(&self) -> bool {
+        !self.is_user_instruction()
+    }
+}
+
+/**
+ * Phase of the current round, derived from the rebalance pipeline flags on VoltVault (and ExtraVoltData for entropy volts).
+ *  short options round:
+ *    RoundEnded -> start_round -> AwaitingNextOption -> set_next_option -> AwaitingRebalancePrepare
+ *    -> rebalance_prepare -> RebalanceEntering -> rebalance_enter (until done) -> SwappingPremium (if premium must be swapped)
+ *    -> rebalance_swap_premium -> AwaitingSettlement -> rebalance_settle -> Settled -> end_round -> RoundEnded
+ *  entropy round:
+ *    RoundEnded -> start_round -> EntropyRoundActive -> setup_rebalance -> EntropyRebalanceReady
+ *    -> rebalance_entropy (until done) -> EntropyDoneRebalancing -> take_performance_fees -> EntropyPerformanceFeesTaken
+ *    -> settle_deposits -> EntropyDepositsResolved -> end_entropy_round -> RoundEnded
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundPhase {
+    Uninitialized,
+    RoundEnded,
+
+    AwaitingNextOption,
+    AwaitingRebalancePrepare,
+    RebalanceEntering,
+    SwappingPremium,
+    AwaitingSettlement,
+    Settled,
+
+    EntropyRoundActive,
+    EntropyRebalanceReady,
+    EntropyDoneRebalancing,
+    EntropyPerformanceFeesTaken,
+    EntropyDepositsResolved,
+}
+
+const USER_INSTRUCTIONS_ROUND_STARTED: &[InstructionKind] = &[
+    InstructionKind::Deposit,
+    InstructionKind::Withdraw,
+    InstructionKind::DepositWithClaim,
+    InstructionKind::WithdrawWithClaim,
+    InstructionKind::ClaimPendingDeposit,
+    InstructionKind::ClaimPendingWithdrawal,
+    InstructionKind::CancelPendingDeposit,
+    InstructionKind::CancelPendingWithdrawal,
+];
+
+const USER_INSTRUCTIONS_ROUND_ENDED: &[InstructionKind] = &[
+    InstructionKind::ClaimPendingDeposit,
+    InstructionKind::ClaimPendingWithdrawal,
+];
+
+impl RoundPhase {
+    /// errors with the program's state error if the flags are in a combination the program never writes
+    pub fn from_volt(volt_vault: &VoltVault, extra_volt_data: &ExtraVoltData) -> Result<Self> {
+        Self::derive(volt_vault, extra_volt_data).map_err(|error_code| error!(error_code))
+    }
+
+    pub(crate) fn derive(
+        volt_vault: &VoltVault,
+        extra_volt_data: &ExtraVoltData,
+    ) -> std::result::Result<Self, ErrorCode> {
+        if !volt_vault.initialized {
+            return Ok(RoundPhase::Uninitialized);
+        }
+
+        if volt_vault.volt_type().is_entropy() {
+            Self::from_entropy_flags(volt_vault, extra_volt_data)
+        } else {
+            Self::from_short_options_flags(volt_vault)
+        }
+    }
+
+    fn from_short_options_flags(volt_vault: &VoltVault) -> std::result::Result<Self, ErrorCode> {
+        if volt_vault.enter_is_finished && !volt_vault.prepare_is_finished {
+            return Err(ErrorCode::InvalidRebalanceEnterState);
+        }
+        if volt_vault.prepare_is_finished && !volt_vault.next_option_was_set {
+            return Err(ErrorCode::InvalidSetNextOptionState);
+        }
+        if volt_vault.must_swap_premium_to_underlying && !volt_vault.enter_is_finished {
+            return Err(ErrorCode::InvalidRebalanceSwapPremiumState);
+        }
+
+        if !volt_vault.round_has_started {
+            if volt_vault.prepare_is_finished {
+                return Err(ErrorCode::InvalidStartRoundState);
+            }
+            return Ok(RoundPhase::RoundEnded);
+        }
+
+        Ok(if !volt_vault.next_option_was_set {
+            RoundPhase::AwaitingNextOption
+        } else if !volt_vault.prepare_is_finished {
+            RoundPhase::AwaitingRebalancePrepare
+        } else if !volt_vault.enter_is_finished {
+            RoundPhase::RebalanceEntering
+        } else if volt_vault.must_swap_premium_to_underlying {
+            RoundPhase::SwappingPremium
+        } else if !volt_vault.curr_option_was_settled {
+            RoundPhase::AwaitingSettlement
+        } else {
+            RoundPhase::Settled
+        })
+    }
+
+    fn from_entropy_flags(
+        volt_vault: &VoltVault,
+        extra_volt_data: &ExtraVoltData,
+    ) -> std::result::Result<Self, ErrorCode> {
+        if extra_volt_data.done_rebalancing && !extra_volt_data.rebalance_is_ready {
+            return Err(ErrorCode::InvalidRebalanceEntropyState);
+        }
+        if extra_volt_data.have_taken_performance_fees && !extra_volt_data.done_rebalancing {
+            return Err(ErrorCode::InvalidTakePerformanceFeesState);
+        }
+        if extra_volt_data.have_resolved_deposits && !extra_volt_data.have_taken_performance_fees {
+            return Err(ErrorCode::InvalidEndEntropyRoundState);
+        }
+
+        if !volt_vault.round_has_started {
+            if extra_volt_data.rebalance_is_ready {
+                return Err(ErrorCode::InvalidStartRoundState);
+            }
+            return Ok(RoundPhase::RoundEnded);
+        }
+
+        Ok(if !extra_volt_data.rebalance_is_ready {
+            RoundPhase::EntropyRoundActive
+        } else if !extra_volt_data.done_rebalancing {
+            RoundPhase::EntropyRebalanceReady
+        } else if !extra_volt_data.have_taken_performance_fees {
+            RoundPhase::EntropyDoneRebalancing
+        } else if !extra_volt_data.have_resolved_deposits {
+            RoundPhase::EntropyPerformanceFeesTaken
+        } else {
+            RoundPhase::EntropyDepositsResolved
+        })
+    }
+
+    pub fn round_has_started(&self) -> bool {
+        !matches!(self, RoundPhase::Uninitialized | RoundPhase::RoundEnded)
+    }
+
+    pub fn legal_user_instructions(&self) -> &'static [InstructionKind] {
+        match self {
+            RoundPhase::Uninitialized => &[],
+            RoundPhase::RoundEnded => USER_INSTRUCTIONS_ROUND_ENDED,
+            _ => USER_INSTRUCTIONS_ROUND_STARTED,
+        }
+    }
+
+    pub fn legal_keeper_instructions(&self) -> &'static [InstructionKind] {
+        match self {
+            RoundPhase::Uninitialized => &[],
+            RoundPhase::RoundEnded => &[InstructionKind::StartRound],
+
+            RoundPhase::AwaitingNextOption => &[InstructionKind::SetNextOption],
+            RoundPhase::AwaitingRebalancePrepare => &[
+                InstructionKind::SetNextOption,
+                InstructionKind::RebalancePrepare,
+            ],
+            RoundPhase::RebalanceEntering => &[InstructionKind::RebalanceEnter],
+            RoundPhase::SwappingPremium => &[InstructionKind::RebalanceSwapPremium],
+            RoundPhase::AwaitingSettlement => &[InstructionKind::RebalanceSettle],
+            RoundPhase::Settled => &[InstructionKind::EndRound],
+
+            RoundPhase::EntropyRoundActive => {
+                &[InstructionKind::SetupRebalance, InstructionKind::ExitEarly]
+            }
+            RoundPhase::EntropyRebalanceReady => &[
+                InstructionKind::RebalanceEntropy,
+                InstructionKind::ExitEarly,
+            ],
+            RoundPhase::EntropyDoneRebalancing => &[InstructionKind::TakePerformanceFees],
+            RoundPhase::EntropyPerformanceFeesTaken => &[InstructionKind::SettleDeposits],
+            RoundPhase::EntropyDepositsResolved => &[InstructionKind::EndEntropyRound],
+        }
+    }
+
+    pub fn is_legal(&self, ix: InstructionKind) -> bool {
+        self.legal_user_instructions().contains(&ix)
+            || self.legal_keeper_instructions().contains(&ix)
+    }
+
+    /// errors with the error the program returns when ix is sent in this phase
+    pub fn check(&self, ix: InstructionKind) -> Result<()> {
+        match self.illegal_instruction_error(ix) {
+            Some(error_code) => Err(error!(error_code)),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn illegal_instruction_error(&self, ix: InstructionKind) -> Option<ErrorCode> {
+        if self.is_legal(ix) {
+            return None;
+        }
+
+        // every user instruction is legal once the round has started
+        Some(match ix {
+            InstructionKind::Deposit
+            | InstructionKind::DepositWithClaim
+            | InstructionKind::Withdraw
+            | InstructionKind::WithdrawWithClaim
+            | InstructionKind::CancelPendingDeposit
+            | InstructionKind::CancelPendingWithdrawal => ErrorCode::RoundHasNotStarted,
+            InstructionKind::ClaimPendingDeposit => ErrorCode::InvalidClaimPendingState,
+            InstructionKind::ClaimPendingWithdrawal => {
+                ErrorCode::InvalidClaimPendingWithdrawalState
+            }
+
+            InstructionKind::StartRound => ErrorCode::InvalidStartRoundState,
+            InstructionKind::SetNextOption => ErrorCode::InvalidSetNextOptionState,
+            InstructionKind::RebalancePrepare => ErrorCode::InvalidRebalancePrepareTime,
+            InstructionKind::RebalanceEnter => ErrorCode::InvalidRebalanceEnterState,
+            InstructionKind::RebalanceSwapPremium => ErrorCode::InvalidRebalanceSwapPremiumState,
+            InstructionKind::RebalanceSettle => ErrorCode::InvalidRebalanceSettleState,
+            InstructionKind::EndRound => ErrorCode::InvalidEndRoundState,
+
+            InstructionKind::SettleDeposits
+            | InstructionKind::RebalanceEntropy
+            | InstructionKind::ExitEarly => ErrorCode::InvalidRebalanceEntropyState,
+            InstructionKind::SetupRebalance => ErrorCode::InvalidSetupRebalanceEntropyState,
+            InstructionKind::TakePerformanceFees => ErrorCode::InvalidTakePerformanceFeesState,
+            InstructionKind::EndEntropyRound => ErrorCode::InvalidEndEntropyRoundState,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn started_volt() -> VoltVault {
+        VoltVault {
+            initialized: true,
+            round_has_started: true,
+            ..VoltVault::default()
+        }
+    }
+
+    fn phase(volt_vault: &VoltVault) -> RoundPhase {
+        RoundPhase::derive(volt_vault, &ExtraVoltData::default()).unwrap()
+    }
+
+    #[test]
+    fn short_options_pipeline() {
+        assert_eq!(phase(&VoltVault::default()), RoundPhase::Uninitialized);
+
+        let mut volt_vault = VoltVault {
+            initialized: true,
+            ..VoltVault::default()
+        };
+        assert_eq!(phase(&volt_vault), RoundPhase::RoundEnded);
+
+        volt_vault.round_has_started = true;
+        assert_eq!(phase(&volt_vault), RoundPhase::AwaitingNextOption);
+        volt_vault.next_option_was_set = true;
+        assert_eq!(phase(&volt_vault), RoundPhase::AwaitingRebalancePrepare);
+        volt_vault.prepare_is_finished = true;
+        assert_eq!(phase(&volt_vault), RoundPhase::RebalanceEntering);
+        volt_vault.enter_is_finished = true;
+        volt_vault.must_swap_premium_to_underlying = true;
+        assert_eq!(phase(&volt_vault), RoundPhase::SwappingPremium);
+        volt_vault.must_swap_premium_to_underlying = false;
+        assert_eq!(phase(&volt_vault), RoundPhase::AwaitingSettlement);
+        volt_vault.curr_option_was_settled = true;
+        assert_eq!(phase(&volt_vault), RoundPhase::Settled);
+    }
+
+    #[test]
+    fn entropy_pipeline() {
+        let volt_vault = VoltVault {
+            vault_type: crate::VoltType::ENTROPY,
+            ..started_volt()
+        };
+        let mut extra_volt_data = ExtraVoltData::default();
+        let phase = |extra_volt_data: &ExtraVoltData| {
+            RoundPhase::derive(&volt_vault, extra_volt_data).unwrap()
+        };
+
+        assert_eq!(phase(&extra_volt_data), RoundPhase::EntropyRoundActive);
+        extra_volt_data.rebalance_is_ready = true;
+        assert_eq!(phase(&extra_volt_data), RoundPhase::EntropyRebalanceReady);
+        extra_volt_data.done_rebalancing = true;
+        assert_eq!(phase(&extra_volt_data), RoundPhase::EntropyDoneRebalancing);
+        extra_volt_data.have_taken_performance_fees = true;
+        assert_eq!(
+            phase(&extra_volt_data),
+            RoundPhase::EntropyPerformanceFeesTaken
+        );
+        extra_volt_data.have_resolved_deposits = true;
+        assert_eq!(phase(&extra_volt_data), RoundPhase::EntropyDepositsResolved);
+    }
+
+    #[test]
+    fn inconsistent_flags() {
+        let volt_vault = VoltVault {
+            enter_is_finished: true,
+            ..started_volt()
+        };
+        assert!(matches!(
+            RoundPhase::derive(&volt_vault, &ExtraVoltData::default()),
+            Err(ErrorCode::InvalidRebalanceEnterState)
+        ));
+
+        let volt_vault = VoltVault {
+            round_has_started: false,
+            next_option_was_set: true,
+            prepare_is_finished: true,
+            ..started_volt()
+        };
+        assert!(matches!(
+            RoundPhase::derive(&volt_vault, &ExtraVoltData::default()),
+            Err(ErrorCode::InvalidStartRoundState)
+        ));
+    }
+
+    #[test]
+    fn illegal_instruction_errors() {
+        let ended = RoundPhase::RoundEnded;
+        assert!(ended.is_legal(InstructionKind::ClaimPendingDeposit));
+        assert!(ended.is_legal(InstructionKind::StartRound));
+        assert!(matches!(
+            ended.illegal_instruction_error(InstructionKind::Deposit),
+            Some(ErrorCode::RoundHasNotStarted)
+        ));
+        assert!(matches!(
+            ended.illegal_instruction_error(InstructionKind::EndRound),
+            Some(ErrorCode::InvalidEndRoundState)
+        ));
+
+        let entering = RoundPhase::RebalanceEntering;
+        assert!(entering.is_legal(InstructionKind::Deposit));
+        assert!(matches!(
+            entering.illegal_instruction_error(InstructionKind::StartRound),
+            Some(ErrorCode::InvalidStartRoundState)
+        ));
+        assert!(RoundPhase::Uninitialized
+            .legal_user_instructions()
+            .is_empty());
+    }
+
+    #[test]
+    fn user_instructions_only_fail_before_the_round_starts() {
+        let started_phases = [
+            RoundPhase::AwaitingNextOption,
+            RoundPhase::AwaitingRebalancePrepare,
+            RoundPhase::RebalanceEntering,
+            RoundPhase::SwappingPremium,
+            RoundPhase::AwaitingSettlement,
+            RoundPhase::Settled,
+            RoundPhase::EntropyRoundActive,
+            RoundPhase::EntropyRebalanceReady,
+            RoundPhase::EntropyDoneRebalancing,
+            RoundPhase::EntropyPerformanceFeesTaken,
+            RoundPhase::EntropyDepositsResolved,
+        ];
+        for phase in started_phases {
+            assert!(phase.round_has_started());
+            for ix in USER_INSTRUCTIONS_ROUND_STARTED {
+                assert!(phase.illegal_instruction_error(*ix).is_none());
+            }
+        }
+
+        for phase in [RoundPhase::Uninitialized, RoundPhase::RoundEnded] {
+            for ix in [
+                InstructionKind::Deposit,
+                InstructionKind::DepositWithClaim,
+                InstructionKind::Withdraw,
+                InstructionKind::WithdrawWithClaim,
+            ] {
+                assert!(matches!(
+                    phase.illegal_instruction_error(ix),
+                    Some(ErrorCode::RoundHasNotStarted)
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,990 @@
+// Serialize/Deserialize for volt account types (feature "serde"), for exporting state as JSON etc.
+//  NOTES:
+//  1. pubkeys are rendered as base58 strings, u64/i64 as decimal strings (JSON numbers lose precision above 2^53). u64/i64 also deserialize from numbers
+//  2. unused_*/extra_key_* padding fields are serialized, unless the account is wrapped in WithoutPadding (which nested structs inherit).
+//     they default to zero if missing when deserializing
+//  3. every struct below lists all of its fields; deserialize builds the struct literal, so a field added to objects.rs without being added here won't compile
+
+use std::fmt;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use serde::de::{self, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::objects::*;
+
+/// implemented by the types below that have (or nest) padding fields. Serialize on those types includes padding
+pub trait SerializePadded {
+    fn serialize_padded<S: Serializer>(
+        &self,
+        serializer: S,
+        include_padding: bool,
+    ) -> Result<S::Ok, S::Error>;
+}
+
+/// serializes the account w/o unused_*/extra_key_* padding fields, e.g serde_json::to_string(&WithoutPadding(&volt_vault))
+#[derive(Clone, Copy, Debug)]
+pub struct WithoutPadding<'a, T>(pub &'a T);
+
+impl<T: SerializePadded> Serialize for WithoutPadding<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_padded(serializer, false)
+    }
+}
+
+// a nested field, serialized with the padding option of its parent
+struct Nested<'a, T>(&'a T, bool);
+
+impl<T: SerializePadded> Serialize for Nested<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_padded(serializer, self.1)
+    }
+}
+
+///////// wire types ///////////
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Base58Pubkey(pub Pubkey);
+
+impl From<Pubkey> for Base58Pubkey {
+    fn from(key: Pubkey) -> Self {
+        Base58Pubkey(key)
+    }
+}
+
+impl From<Base58Pubkey> for Pubkey {
+    fn from(key: Base58Pubkey) -> Self {
+        key.0
+    }
+}
+
+impl Serialize for Base58Pubkey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Base58Pubkey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PubkeyVisitor;
+
+        impl<'de> Visitor<'de> for PubkeyVisitor {
+            type Value = Base58Pubkey;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a base58 encoded pubkey")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Base58Pubkey, E> {
+                Pubkey::from_str(v)
+                    .map(Base58Pubkey)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_str(PubkeyVisitor)
+    }
+}
+
+macro_rules! string_integer {
+    ($wire:ident, $int:ty, $visit:ident, $other_int:ty, $visit_other:ident, $expecting:literal) => {
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct $wire(pub $int);
+
+        impl From<$int> for $wire {
+            fn from(v: $int) -> Self {
+                $wire(v)
+            }
+        }
+
+        impl From<$wire> for $int {
+            fn from(v: $wire) -> Self {
+                v.0
+            }
+        }
+
+        impl Serialize for $wire {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $wire {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct IntVisitor;
+
+                impl<'de> Visitor<'de> for IntVisitor {
+                    type Value = $wire;
+
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str($expecting)
+                    }
+
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<$wire, E> {
+                        v.parse()
+                            .map($wire)
+                            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+                    }
+
+                    fn $visit<E: de::Error>(self, v: $int) -> Result<$wire, E> {
+                        Ok($wire(v))
+                    }
+
+                    fn $visit_other<E: de::Error>(self, v: $other_int) -> Result<$wire, E> {
+                        <$int>::try_from(v)
+                            .map($wire)
+                            .map_err(|_| E::custom(concat!("out of range for ", stringify!($int))))
+                    }
+                }
+
+                deserializer.deserialize_any(IntVisitor)
+            }
+        }
+    };
+}
+
+string_integer!(
+    U64String,
+    u64,
+    visit_u64,
+    i64,
+    visit_i64,
+    "a u64 as a decimal string or number"
+);
+string_integer!(
+    I64String,
+    i64,
+    visit_i64,
+    u64,
+    visit_u64,
+    "an i64 as a decimal string or number"
+);
+
+///////// structs ///////////
+
+// fields are `name: WireType`, where WireType converts from/into the field's type. #[padding] fields may be omitted,
+// #[nested] fields are SerializePadded types serialized with the parent's padding option
+macro_rules! serde_struct {
+    ($name:ident { $( $(#[$kind:ident])? $field:ident: $wire:ty, )* }) => {
+        const _: () = {
+            const FIELDS: &[&str] = &[$(stringify!($field)),*];
+
+            impl SerializePadded for $name {
+                // unused by structs w/o padding
+                #[allow(unused_variables)]
+                fn serialize_padded<S: Serializer>(
+                    &self,
+                    serializer: S,
+                    include_padding: bool,
+                ) -> Result<S::Ok, S::Error> {
+                    let mut state = serializer.serialize_struct(stringify!($name), FIELDS.len())?;
+                    $(
+                        serde_struct!(@serialize self, state, include_padding, $($kind)?, $field: $wire);
+                    )*
+                    state.end()
+                }
+            }
+
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.serialize_padded(serializer, true)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct StructVisitor;
+
+                    impl<'de> Visitor<'de> for StructVisitor {
+                        type Value = $name;
+
+                        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                            f.write_str(concat!("struct ", stringify!($name)))
+                        }
+
+                        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<$name, A::Error> {
+                            $( let mut $field: Option<$wire> = None; )*
+                            while let Some(key) = map.next_key::<String>()? {
+                                match key.as_str() {
+                                    $(
+                                        stringify!($field) => {
+                                            if $field.is_some() {
+                                                return Err(de::Error::duplicate_field(stringify!($field)));
+                                            }
+                                            $field = Some(map.next_value()?);
+                                        }
+                                    )*
+                                    _ => {
+                                        map.next_value::<IgnoredAny>()?;
+                                    }
+                                }
+                            }
+                            Ok($name {
+                                $( $field: serde_struct!(@field $field, $($kind)?).into(), )*
+                            })
+                        }
+                    }
+
+                    deserializer.deserialize_struct(stringify!($name), FIELDS, StructVisitor)
+                }
+            }
+        };
+    };
+    (@serialize $self:ident, $state:ident, $include_padding:ident, padding, $field:ident: $wire:ty) => {
+        if $include_padding {
+            $state.serialize_field(stringify!($field), &<$wire>::from($self.$field.clone()))?;
+        } else {
+            $state.skip_field(stringify!($field))?;
+        }
+    };
+    (@serialize $self:ident, $state:ident, $include_padding:ident, nested, $field:ident: $wire:ty) => {
+        $state.serialize_field(stringify!($field), &Nested(&$self.$field, $include_padding))?;
+    };
+    (@serialize $self:ident, $state:ident, $include_padding:ident, , $field:ident: $wire:ty) => {
+        $state.serialize_field(stringify!($field), &<$wire>::from($self.$field.clone()))?;
+    };
+    (@field $field:ident, padding) => {
+        $field.unwrap_or_default()
+    };
+    (@field $field:ident, $($kind:ident)?) => {
+        $field.ok_or_else(|| de::Error::missing_field(stringify!($field)))?
+    };
+}
+
+serde_struct!(VoltVault {
+    admin_key: Base58Pubkey,
+    seed: Base58Pubkey,
+    transfer_window: U64String,
+    start_transfer_time: U64String,
+    end_transfer_time: U64String,
+    initialized: bool,
+    curr_option_was_settled: bool,
+    must_swap_premium_to_underlying: bool,
+    next_option_was_set: bool,
+    first_ever_option_was_set: bool,
+    instant_transfers_enabled: bool,
+    prepare_is_finished: bool,
+    enter_is_finished: bool,
+    round_has_started: bool,
+    round_number: U64String,
+    total_underlying_pre_enter: U64String,
+    total_underlying_post_settle: U64String,
+    total_volt_tokens_post_settle: U64String,
+    vault_authority: Base58Pubkey,
+    deposit_pool: Base58Pubkey,
+    premium_pool: Base58Pubkey,
+    option_pool: Base58Pubkey,
+    writer_token_pool: Base58Pubkey,
+    vault_mint: Base58Pubkey,
+    underlying_asset_mint: Base58Pubkey,
+    quote_asset_mint: Base58Pubkey,
+    option_mint: Base58Pubkey,
+    writer_token_mint: Base58Pubkey,
+    option_market: Base58Pubkey,
+    vault_type: U64String,
+    underlying_amount_per_contract: U64String,
+    quote_amount_per_contract: U64String,
+    expiration_unix_timestamp: I64String,
+    expiration_interval: U64String,
+    upper_bound_otm_strike_factor: U64String,
+    have_taken_withdrawal_fees: bool,
+    serum_spot_market: Base58Pubkey,
+    open_orders_bump: u8,
+    open_orders_init_bump: u8,
+    ul_open_orders_bump: u8,
+    ul_open_orders: Base58Pubkey,
+    ul_open_orders_initialized: bool,
+    bump_authority: u8,
+    serum_order_size_options: U64String,
+    individual_capacity: U64String,
+    serum_order_type: U64String,
+    serum_limit: u16,
+    serum_self_trade_behavior: u16,
+    serum_client_order_id: U64String,
+    whitelist_token_mint: Base58Pubkey,
+    permissioned_market_premium_mint: Base58Pubkey,
+    permissioned_market_premium_pool: Base58Pubkey,
+    capacity: U64String,
+});
+
+serde_struct!(ExtraVoltData {
+    is_whitelisted: bool,
+    whitelist: Base58Pubkey,
+    is_for_dao: bool,
+    dao_program_id: Base58Pubkey,
+    deposit_mint: Base58Pubkey,
+    target_leverage: f64,
+    target_leverage_lenience: f64,
+    exit_early_ratio: f64,
+    entropy_program_id: Base58Pubkey,
+    entropy_group: Base58Pubkey,
+    entropy_account: Base58Pubkey,
+    power_perp_market: Base58Pubkey,
+    have_resolved_deposits: bool,
+    done_rebalancing: bool,
+    non_payer_authority: Base58Pubkey,
+    serum_program_id: Base58Pubkey,
+    entropy_cache: Base58Pubkey,
+    spot_perp_market: Base58Pubkey,
+    #[padding]
+    extra_key_7: Base58Pubkey,
+    #[padding]
+    extra_key_8: Base58Pubkey,
+    #[padding]
+    extra_key_9: Base58Pubkey,
+    #[padding]
+    extra_key_10: Base58Pubkey,
+    #[padding]
+    extra_key_11: Base58Pubkey,
+    #[padding]
+    extra_key_12: Base58Pubkey,
+    #[padding]
+    extra_key_13: Base58Pubkey,
+    #[padding]
+    extra_key_14: Base58Pubkey,
+    net_withdrawals: U64String,
+    max_quote_pos_change: U64String,
+    target_hedge_lenience: f64,
+    #[padding]
+    unused_uint_four: U64String,
+    #[padding]
+    unused_uint_five: U64String,
+    #[padding]
+    unused_uint_six: U64String,
+    #[padding]
+    unused_uint_7: U64String,
+    #[padding]
+    unused_uint_8: U64String,
+    #[padding]
+    unused_uint_9: U64String,
+    #[padding]
+    unused_uint_10: U64String,
+    #[padding]
+    unused_uint_11: U64String,
+    #[padding]
+    unused_uint_12: U64String,
+    turn_off_deposits_and_withdrawals: bool,
+    rebalance_is_ready: bool,
+    #[padding]
+    unused_bool1234: bool,
+    done_rebalancing_power_perp: bool,
+    is_hedging_on: bool,
+    have_taken_performance_fees: bool,
+});
+
+serde_struct!(Round {
+    number: U64String,
+    underlying_from_pending_deposits: U64String,
+    volt_tokens_from_pending_withdrawals: U64String,
+    underlying_pre_enter: U64String,
+    underlying_post_settle: U64String,
+    premium_farmed: U64String,
+});
+
+serde_struct!(FriktionEpochInfo {
+    vault_token_price: f64,
+    pct_pnl: f64,
+    number: U64String,
+    underlying_pre_enter: U64String,
+    underlying_post_settle: U64String,
+    volt_token_supply: U64String,
+    pnl: I64String,
+    performance_fees: U64String,
+    withdrawal_fees: U64String,
+    pending_deposits: U64String,
+    pending_withdrawals_volt_tokens: U64String,
+    pending_withdrawals: U64String,
+    canceled_withdrawals: U64String,
+    canceled_deposits: U64String,
+    total_withdrawals: U64String,
+    total_deposits: U64String,
+    instant_deposits: U64String,
+    instant_withdrawals: U64String,
+    #[padding]
+    unused_uint_1: U64String,
+    minted_options: U64String,
+    enter_num_times_called: U64String,
+    swap_premium_num_times_called: U64String,
+    option_key: Base58Pubkey,
+    #[padding]
+    extra_key_four: Base58Pubkey,
+    #[padding]
+    extra_key_5: Base58Pubkey,
+    #[padding]
+    extra_key_6: Base58Pubkey,
+    #[padding]
+    extra_key_7: Base58Pubkey,
+    #[padding]
+    extra_key_8: Base58Pubkey,
+    #[padding]
+    extra_key_9: Base58Pubkey,
+    #[padding]
+    extra_key_10: Base58Pubkey,
+    #[padding]
+    extra_key_11: Base58Pubkey,
+    #[padding]
+    extra_key_12: Base58Pubkey,
+    #[padding]
+    unused_uint_four: U64String,
+    #[padding]
+    unused_uint_five: U64String,
+    #[padding]
+    unused_uint_six: U64String,
+    #[padding]
+    unused_uint_7: U64String,
+    #[padding]
+    unused_uint_8: U64String,
+    #[padding]
+    unused_uint_9: U64String,
+    #[padding]
+    unused_uint_10: U64String,
+    #[padding]
+    unused_uint_11: U64String,
+    #[padding]
+    unused_uint_12: U64String,
+    #[padding]
+    unused_bool_one: bool,
+    #[padding]
+    unused_bool_two: bool,
+    #[padding]
+    unused_bool_three: bool,
+    #[padding]
+    unused_bool_four: bool,
+    #[padding]
+    unused_bool_five: bool,
+    #[padding]
+    unused_bool_six: bool,
+});
+
+serde_struct!(EntropyRound {
+    instant_deposits_native: U64String,
+    prev_entropy_account_deposits: U64String,
+    initial_equity: f64,
+    new_equity_post_deposit: f64,
+    deposit_amt: f64,
+    withdraw_comp_from_deposit: U64String,
+    net_deposits: f64,
+    deposit_amt_native: U64String,
+    withdraw_amt_native: U64String,
+    total_volt_supply: U64String,
+    oracle_price: f64,
+    acct_equity_start: f64,
+    acct_equity_before_next_rebalance: f64,
+    pnl_quote: f64,
+    performance_fees_quote: f64,
+    temp1: Base58Pubkey,
+    temp2: Base58Pubkey,
+    temp3: Base58Pubkey,
+    #[padding]
+    extra_key_11: Base58Pubkey,
+    #[padding]
+    extra_key_12: Base58Pubkey,
+    #[padding]
+    unused_uint_four: U64String,
+    #[padding]
+    unused_uint_five: U64String,
+    #[padding]
+    unused_uint_six: U64String,
+    #[padding]
+    unused_uint_12: U64String,
+    #[padding]
+    unused_float1: f64,
+    #[padding]
+    unused_float2: f64,
+    #[padding]
+    unused_float3: f64,
+    #[padding]
+    unused_float4: f64,
+    #[padding]
+    unused_bool_one: bool,
+    #[padding]
+    unused_bool_two: bool,
+    #[padding]
+    unused_bool_three: bool,
+    #[padding]
+    unused_bool_four: bool,
+});
+
+serde_struct!(EntropyMetadata {
+    target_hedge_ratio: f64,
+    rebalancing_lenience: f64,
+    required_basis_from_oracle: f64,
+    #[padding]
+    extra_key_3: Base58Pubkey,
+    #[padding]
+    extra_key_4: Base58Pubkey,
+    #[padding]
+    extra_key_5: Base58Pubkey,
+    #[padding]
+    extra_key_6: Base58Pubkey,
+    #[padding]
+    extra_key_7: Base58Pubkey,
+    #[padding]
+    extra_key_8: Base58Pubkey,
+    #[padding]
+    extra_key_9: Base58Pubkey,
+    #[padding]
+    extra_key_10: Base58Pubkey,
+    #[padding]
+    extra_key_11: Base58Pubkey,
+    #[padding]
+    extra_key_12: Base58Pubkey,
+    #[padding]
+    unused_uint_four: U64String,
+    #[padding]
+    unused_uint_five: U64String,
+    #[padding]
+    unused_uint_six: U64String,
+    #[padding]
+    unused_uint_12: U64String,
+    #[padding]
+    unused_uint_123: U64String,
+    #[padding]
+    unused_uint_456: U64String,
+    #[padding]
+    unused_uint_789: U64String,
+    #[padding]
+    unused_uint_102: U64String,
+    #[padding]
+    unused_float1: f64,
+    #[padding]
+    unused_float2: f64,
+    #[padding]
+    unused_float3: f64,
+    #[padding]
+    unused_float4: f64,
+    #[padding]
+    unused_float5: f64,
+    #[padding]
+    unused_float6: f64,
+    #[padding]
+    unused_float7: f64,
+    #[padding]
+    unused_float8: f64,
+    #[padding]
+    unused_float9: f64,
+    #[padding]
+    unused_float10: f64,
+    #[padding]
+    unused_float11: f64,
+    #[padding]
+    unused_float12: f64,
+    #[padding]
+    unused_bool_one: bool,
+    #[padding]
+    unused_bool_two: bool,
+    #[padding]
+    unused_bool_three: bool,
+    #[padding]
+    unused_bool_four: bool,
+    #[padding]
+    unused_bool_five: bool,
+    #[padding]
+    unused_bool_six: bool,
+    #[padding]
+    unused_bool_seven: bool,
+    #[padding]
+    unused_bool_eight: bool,
+    #[padding]
+    unused_bool_nine: bool,
+    #[padding]
+    unused_bool_ten: bool,
+    vault_name: String,
+});
+
+serde_struct!(PrincipalProtectionVaultV1 {
+    initialized: bool,
+    vault_name: String,
+    #[nested]
+    keys: PrincipalProtectionAccountsV1,
+    allocation_strategy: SecondLegAllocationStrategy,
+    #[nested]
+    lending_strategy: LendingStrategy,
+});
+
+serde_struct!(PrincipalProtectionAccountsV1 {
+    #[nested]
+    lending_keys: LendingKeys,
+    #[nested]
+    options_keys: OptionsContractKeys,
+    lending_shares_pool: Base58Pubkey,
+    deposit_into_lending_ata: Base58Pubkey,
+    #[padding]
+    extra_key1: Base58Pubkey,
+    #[padding]
+    extra_key2: Base58Pubkey,
+    #[padding]
+    extra_key3: Base58Pubkey,
+    #[padding]
+    extra_key4: Base58Pubkey,
+    #[padding]
+    extra_key5: Base58Pubkey,
+    #[padding]
+    extra_key6: Base58Pubkey,
+    #[padding]
+    extra_key7: Base58Pubkey,
+});
+
+serde_struct!(PrimaryVaultKeys {
+    vault: Base58Pubkey,
+    vault_pda: Base58Pubkey,
+    underlying_deposit_queue: Base58Pubkey,
+    underlying_withdraw_queue: Base58Pubkey,
+    shares_mint: Base58Pubkey,
+    underlying_mint: Base58Pubkey,
+    deposit_tracking_account: Base58Pubkey,
+    deposit_tracking_queue_account: Base58Pubkey,
+    deposit_tracking_hold_account: Base58Pubkey,
+    deposit_tracking_pda: Base58Pubkey,
+    program_id: Base58Pubkey,
+    #[padding]
+    extra_key_1: Base58Pubkey,
+    #[padding]
+    extra_key_2: Base58Pubkey,
+    #[padding]
+    extra_key_3: Base58Pubkey,
+    #[padding]
+    extra_key_4: Base58Pubkey,
+    #[padding]
+    extra_key_5: Base58Pubkey,
+    #[padding]
+    extra_key_6: Base58Pubkey,
+});
+
+serde_struct!(LendingKeys {
+    #[nested]
+    primary_vault: PrimaryVaultKeys,
+    mango_vault: Base58Pubkey,
+    solend_vault: Base58Pubkey,
+    tulip_vault: Base58Pubkey,
+});
+
+serde_struct!(OptionsContractKeys {
+    program_id: Base58Pubkey,
+    options_contract: Base58Pubkey,
+    option_token_pool: Base58Pubkey,
+    #[padding]
+    extra_key1: Base58Pubkey,
+    #[padding]
+    extra_key2: Base58Pubkey,
+    #[padding]
+    extra_key3: Base58Pubkey,
+    #[padding]
+    extra_key4: Base58Pubkey,
+});
+
+serde_struct!(LendingParams {
+    bool1: u8,
+    bool2: u8,
+    bool3: u8,
+    bool4: u8,
+    max_allowed_utilization_bps: U64String,
+    #[padding]
+    unused_uint1: U64String,
+    #[padding]
+    unused_uint2: U64String,
+    #[padding]
+    unused_uint3: U64String,
+    #[padding]
+    unused_uint4: U64String,
+    #[padding]
+    unused_float1: f64,
+    #[padding]
+    unused_float2: f64,
+    #[padding]
+    unused_float3: f64,
+    #[padding]
+    unused_float4: f64,
+});
+
+///////// enums ///////////
+
+// externally tagged like serde's derive, e.g {"MinApr":{"apr":"1000"}}
+#[derive(Serialize, Deserialize)]
+enum SecondLegAllocationStrategyRepr {
+    MinApr {
+        apr: U64String,
+    },
+    ProjectedPnlFraction {
+        fraction_bps: U64String,
+    },
+    FixedFraction {
+        fraction_bps: U64String,
+    },
+    ExtraStrategy1 {
+        uint1: U64String,
+        uint2: U64String,
+        uint3: U64String,
+        uint4: U64String,
+        u81: u8,
+        u82: u8,
+        u83: u8,
+        u84: u8,
+    },
+}
+
+impl From<SecondLegAllocationStrategy> for SecondLegAllocationStrategyRepr {
+    fn from(strategy: SecondLegAllocationStrategy) -> Self {
+        match strategy {
+            SecondLegAllocationStrategy::MinApr { apr } => {
+                SecondLegAllocationStrategyRepr::MinApr { apr: apr.into() }
+            }
+            SecondLegAllocationStrategy::ProjectedPnlFraction { fraction_bps } => {
+                SecondLegAllocationStrategyRepr::ProjectedPnlFraction {
+                    fraction_bps: fraction_bps.into(),
+                }
+            }
+            SecondLegAllocationStrategy::FixedFraction { fraction_bps } => {
+                SecondLegAllocationStrategyRepr::FixedFraction {
+                    fraction_bps: fraction_bps.into(),
+                }
+            }
+            SecondLegAllocationStrategy::ExtraStrategy1 {
+                uint1,
+                uint2,
+                uint3,
+                uint4,
+                u81,
+                u82,
+                u83,
+                u84,
+            } => SecondLegAllocationStrategyRepr::ExtraStrategy1 {
+                uint1: uint1.into(),
+                uint2: uint2.into(),
+                uint3: uint3.into(),
+                uint4: uint4.into(),
+                u81,
+                u82,
+                u83,
+                u84,
+            },
+        }
+    }
+}
+
+impl From<SecondLegAllocationStrategyRepr> for SecondLegAllocationStrategy {
+    fn from(strategy: SecondLegAllocationStrategyRepr) -> Self {
+        match strategy {
+            SecondLegAllocationStrategyRepr::MinApr { apr } => {
+                SecondLegAllocationStrategy::MinApr { apr: apr.into() }
+            }
+            SecondLegAllocationStrategyRepr::ProjectedPnlFraction { fraction_bps } => {
+                SecondLegAllocationStrategy::ProjectedPnlFraction {
+                    fraction_bps: fraction_bps.into(),
+                }
+            }
+            SecondLegAllocationStrategyRepr::FixedFraction { fraction_bps } => {
+                SecondLegAllocationStrategy::FixedFraction {
+                    fraction_bps: fraction_bps.into(),
+                }
+            }
+            SecondLegAllocationStrategyRepr::ExtraStrategy1 {
+                uint1,
+                uint2,
+                uint3,
+                uint4,
+                u81,
+                u82,
+                u83,
+                u84,
+            } => SecondLegAllocationStrategy::ExtraStrategy1 {
+                uint1: uint1.into(),
+                uint2: uint2.into(),
+                uint3: uint3.into(),
+                uint4: uint4.into(),
+                u81,
+                u82,
+                u83,
+                u84,
+            },
+        }
+    }
+}
+
+impl Serialize for SecondLegAllocationStrategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SecondLegAllocationStrategyRepr::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecondLegAllocationStrategy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SecondLegAllocationStrategyRepr::deserialize(deserializer).map(Into::into)
+    }
+}
+
+// params is LendingParams when deserializing, Nested<LendingParams> when serializing
+#[derive(Serialize, Deserialize)]
+enum LendingStrategyRepr<P> {
+    TulipOptimizer { params: P },
+}
+
+impl SerializePadded for LendingStrategy {
+    fn serialize_padded<S: Serializer>(
+        &self,
+        serializer: S,
+        include_padding: bool,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            LendingStrategy::TulipOptimizer { params } => LendingStrategyRepr::TulipOptimizer {
+                params: Nested(params, include_padding),
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for LendingStrategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize_padded(serializer, true)
+    }
+}
+
+impl<'de> Deserialize<'de> for LendingStrategy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match LendingStrategyRepr::<LendingParams>::deserialize(deserializer)? {
+            LendingStrategyRepr::TulipOptimizer { params } => {
+                Ok(LendingStrategy::TulipOptimizer { params })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AnchorDeserialize;
+    use serde_json::json;
+
+    fn principal_protection_vault() -> PrincipalProtectionVaultV1 {
+        // borsh decodes zeroes as the first enum variants and an empty vault_name
+        let mut vault =
+            <PrincipalProtectionVaultV1 as AnchorDeserialize>::deserialize(&mut &[0u8; 4096][..])
+                .unwrap();
+        vault.vault_name = "pp".to_string();
+        vault.keys.extra_key1 = Pubkey::new_unique();
+        vault.keys.lending_keys.primary_vault.vault = Pubkey::new_unique();
+        vault.lending_strategy = LendingStrategy::TulipOptimizer {
+            params: LendingParams {
+                max_allowed_utilization_bps: 8_000,
+                unused_uint1: 7,
+                ..LendingParams::default()
+            },
+        };
+        vault
+    }
+
+    #[test]
+    fn pubkeys_and_integers_as_strings() {
+        let volt_vault = VoltVault {
+            admin_key: Pubkey::new_unique(),
+            capacity: u64::MAX,
+            expiration_unix_timestamp: i64::MIN,
+            serum_limit: 3,
+            ..VoltVault::default()
+        };
+        let value = serde_json::to_value(volt_vault).unwrap();
+        assert_eq!(value["admin_key"], json!(volt_vault.admin_key.to_string()));
+        assert_eq!(value["capacity"], json!("18446744073709551615"));
+        assert_eq!(
+            value["expiration_unix_timestamp"],
+            json!(i64::MIN.to_string())
+        );
+        assert_eq!(value["serum_limit"], json!(3));
+
+        let round_trip: VoltVault = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(round_trip).unwrap(), value);
+        assert_eq!(round_trip.capacity, u64::MAX);
+    }
+
+    #[test]
+    fn integers_deserialize_from_numbers() {
+        let round: Round = serde_json::from_value(json!({
+            "number": 5,
+            "underlying_from_pending_deposits": "6",
+            "volt_tokens_from_pending_withdrawals": 0,
+            "underlying_pre_enter": 0,
+            "underlying_post_settle": 0,
+            "premium_farmed": 0,
+            "unknown_field": [1, 2],
+        }))
+        .unwrap();
+        assert_eq!(round.number, 5);
+        assert_eq!(round.underlying_from_pending_deposits, 6);
+
+        let missing_field = serde_json::from_value::<Round>(json!({ "number": 5 }));
+        assert!(missing_field
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("missing field"));
+        let negative = serde_json::from_value::<U64String>(json!(-1));
+        assert!(negative.is_err());
+        let not_a_pubkey = serde_json::from_value::<Base58Pubkey>(json!("not a pubkey"));
+        assert!(not_a_pubkey.is_err());
+    }
+
+    #[test]
+    fn without_padding() {
+        let extra_volt_data = ExtraVoltData {
+            extra_key_7: Pubkey::new_unique(),
+            unused_uint_four: 4,
+            net_withdrawals: 9,
+            ..ExtraVoltData::default()
+        };
+        let padded = serde_json::to_value(extra_volt_data).unwrap();
+        assert_eq!(
+            padded["extra_key_7"],
+            json!(extra_volt_data.extra_key_7.to_string())
+        );
+        assert_eq!(padded["unused_uint_four"], json!("4"));
+
+        let unpadded = serde_json::to_value(WithoutPadding(&extra_volt_data)).unwrap();
+        assert!(unpadded.get("extra_key_7").is_none());
+        assert!(unpadded.get("unused_uint_four").is_none());
+        assert_eq!(unpadded["net_withdrawals"], json!("9"));
+
+        // padding defaults to zero
+        let round_trip: ExtraVoltData = serde_json::from_value(unpadded).unwrap();
+        assert_eq!(round_trip.extra_key_7, Pubkey::default());
+        assert_eq!(round_trip.unused_uint_four, 0);
+        assert_eq!(round_trip.net_withdrawals, 9);
+    }
+
+    #[test]
+    fn nested_structs_inherit_padding_option() {
+        let vault = principal_protection_vault();
+        let padded = serde_json::to_value(&vault).unwrap();
+        assert_eq!(
+            padded["keys"]["extra_key1"],
+            json!(vault.keys.extra_key1.to_string())
+        );
+        assert_eq!(
+            padded["lending_strategy"]["TulipOptimizer"]["params"]["unused_uint1"],
+            json!("7")
+        );
+
+        let unpadded = serde_json::to_value(WithoutPadding(&vault)).unwrap();
+        assert!(unpadded["keys"].get("extra_key1").is_none());
+        let params = &unpadded["lending_strategy"]["TulipOptimizer"]["params"];
+        assert!(params.get("unused_uint1").is_none());
+        assert_eq!(params["max_allowed_utilization_bps"], json!("8000"));
+        assert_eq!(
+            unpadded["keys"]["lending_keys"]["primary_vault"]["vault"],
+            json!(vault.keys.lending_keys.primary_vault.vault.to_string())
+        );
+
+        let round_trip: PrincipalProtectionVaultV1 =
+            serde_json::from_value(padded.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&round_trip).unwrap(), padded);
+        let round_trip: PrincipalProtectionVaultV1 = serde_json::from_value(unpadded).unwrap();
+        assert_eq!(round_trip.vault_name, "pp");
+        assert_eq!(round_trip.keys.extra_key1, Pubkey::default());
+        assert!(matches!(
+            round_trip.lending_strategy,
+            LendingStrategy::TulipOptimizer { params } if params.unused_uint1 == 0
+        ));
+    }
+}
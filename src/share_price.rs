@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::VoltVault;
+
+/**
+ * Underlying and volt token totals that define the price of a volt token.
+ *  NOTES:
+ *  1. all conversions use u128 intermediates and round down (in favor of the volt), as the program does
+ *  2. if no volt tokens exist, deposits are minted 1:1
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SharePrice {
+    pub total_underlying: u64,
+    pub total_volt_tokens: u64,
+}
+
+impl SharePrice {
+    // price as of the last settlement, used for pending deposits and withdrawals
+    pub fn post_settle(volt_vault: &VoltVault) -> Self {
+        SharePrice {
+            total_underlying: volt_vault.total_underlying_post_settle,
+            total_volt_tokens: volt_vault.total_volt_tokens_post_settle,
+        }
+    }
+
+    // live price, used for instant transfers
+    pub fn from_balances(vault_mint_supply: u64, deposit_pool_balance: u64) -> Self {
+        SharePrice {
+            total_underlying: deposit_pool_balance,
+            total_volt_tokens: vault_mint_supply,
+        }
+    }
+
+    pub fn volt_tokens_for_underlying(&self, underlying_amount: u64) -> Result<u64> {
+        if self.total_volt_tokens == 0 {
+            return Ok(underlying_amount);
+        }
+        if self.total_underlying == 0 {
+            return err!(ErrorCode::VaultMintSupplyMustBeZeroIfEquityIsZero);
+        }
+        mul_div_floor(
+            underlying_amount,
+            self.total_volt_tokens,
+            self.total_underlying,
+        )
+    }
+
+    pub fn underlying_for_volt_tokens(&self, volt_token_amount: u64) -> Result<u64> {
+        if volt_token_amount > self.total_volt_tokens {
+            return err!(ErrorCode::InsufficientVaultTokens);
+        }
+        if volt_token_amount == 0 {
+            return Ok(0);
+        }
+        mul_div_floor(
+            volt_token_amount,
+            self.total_underlying,
+            self.total_volt_tokens,
+        )
+    }
+}
+
+// floor(a * b / c), erroring on a zero denominator or a result that does not fit in a u64
+pub fn mul_div_floor(a: u64, b: u64, c: u64) -> Result<u64> {
+    let result = (a as u128)
+        .checked_mul(b as u128)
+        .and_then(|numerator| numerator.checked_div(c as u128))
+        .ok_or_else(|| error!(ErrorCode::NumberOverflow))?;
+    u64::try_from(result).map_err(|_| error!(ErrorCode::NumberOverflow))
+}
+
+pub(crate) fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .ok_or_else(|| error!(ErrorCode::NumberOverflow))
+}
+
+pub(crate) fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b)
+        .ok_or_else(|| error!(ErrorCode::NumberOverflow))
+}
+
+/// ESTIMATE of the volt tokens a pending deposit of deposit_amount will be claimable for.
+/// pending deposits are minted at the price of the next settlement, which is not known until
+/// the round ends, so this prices the deposit at the last settlement instead.
+/// once the round has ended, PendingDeposit::claimable_volt_tokens gives the exact amount
+pub fn preview_deposit(volt_vault: &VoltVault, deposit_amount: u64) -> Result<u64> {
+    SharePrice::post_settle(volt_vault).volt_tokens_for_underlying(deposit_amount)
+}
+
+/// volt tokens minted immediately by an instant deposit
+pub fn preview_instant_deposit(
+    vault_mint_supply: u64,
+    deposit_pool_balance: u64,
+    deposit_amount: u64,
+) -> Result<u64> {
+    SharePrice::from_balances(vault_mint_supply, deposit_pool_balance)
+        .volt_tokens_for_underlying(deposit_amount)
+}
+
+/// ESTIMATE of the underlying a pending withdrawal of volt_token_amount will be claimable for,
+/// priced at the last settlement for the same reason as preview_deposit. does not include
+/// withdrawal fees. once the round has ended, PendingWithdrawal::claimable_underlying gives the exact amount
+pub fn preview_withdraw(volt_vault: &VoltVault, volt_token_amount: u64) -> Result<u64> {
+    SharePrice::post_settle(volt_vault).underlying_for_volt_tokens(volt_token_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_error(result: Result<u64>, error_code: ErrorCode) -> bool {
+        result.unwrap_err() == error!(error_code)
+    }
+
+    #[test]
+    fn rounds_down() {
+        // 3 volt tokens for 2 underlying
+        let price = SharePrice {
+            total_underlying: 2,
+            total_volt_tokens: 3,
+        };
+        assert_eq!(price.volt_tokens_for_underlying(1).unwrap(), 1);
+        assert_eq!(price.volt_tokens_for_underlying(2).unwrap(), 3);
+        assert_eq!(price.underlying_for_volt_tokens(1).unwrap(), 0);
+        assert_eq!(price.underlying_for_volt_tokens(2).unwrap(), 1);
+        assert_eq!(price.underlying_for_volt_tokens(3).unwrap(), 2);
+
+        // exact boundary: no rounding at a whole multiple
+        let price = SharePrice {
+            total_underlying: 1_000_000,
+            total_volt_tokens: 999_999,
+        };
+        assert_eq!(
+            price.volt_tokens_for_underlying(1_000_000).unwrap(),
+            999_999
+        );
+        assert_eq!(price.volt_tokens_for_underlying(999_999).unwrap(), 999_998);
+    }
+
+    #[test]
+    fn empty_volt() {
+        let price = SharePrice::default();
+        assert_eq!(price.volt_tokens_for_underlying(7).unwrap(), 7);
+        assert_eq!(price.underlying_for_volt_tokens(0).unwrap(), 0);
+        assert!(is_error(
+            price.underlying_for_volt_tokens(1),
+            ErrorCode::InsufficientVaultTokens
+        ));
+
+        let price = SharePrice {
+            total_underlying: 0,
+            total_volt_tokens: 1,
+        };
+        assert!(is_error(
+            price.volt_tokens_for_underlying(1),
+            ErrorCode::VaultMintSupplyMustBeZeroIfEquityIsZero
+        ));
+    }
+
+    #[test]
+    fn mul_div_floor_overflow() {
+        assert_eq!(
+            mul_div_floor(u64::MAX, u64::MAX, u64::MAX).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(mul_div_floor(u64::MAX, 2, 3).unwrap(), u64::MAX / 3 * 2);
+        assert!(is_error(
+            mul_div_floor(u64::MAX, 2, 1),
+            ErrorCode::NumberOverflow
+        ));
+        assert!(is_error(mul_div_floor(1, 1, 0), ErrorCode::NumberOverflow));
+        assert!(is_error(
+            checked_add(u64::MAX, 1),
+            ErrorCode::NumberOverflow
+        ));
+        assert!(is_error(checked_sub(0, 1), ErrorCode::NumberOverflow));
+
+        let price = SharePrice {
+            total_underlying: 1,
+            total_volt_tokens: u64::MAX,
+        };
+        assert!(is_error(
+            price.volt_tokens_for_underlying(2),
+            ErrorCode::NumberOverflow
+        ));
+    }
+
+    #[test]
+    fn previews() {
+        let volt_vault = VoltVault {
+            total_underlying_post_settle: 300,
+            total_volt_tokens_post_settle: 200,
+            ..VoltVault::default()
+        };
+        assert_eq!(preview_deposit(&volt_vault, 100).unwrap(), 66);
+        assert_eq!(preview_withdraw(&volt_vault, 100).unwrap(), 150);
+        assert_eq!(preview_instant_deposit(200, 300, 100).unwrap(), 66);
+        assert_eq!(preview_instant_deposit(0, 0, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn matches_claim_when_price_unchanged() {
+        // if the price does not move over the round, the estimate equals what is claimable
+        let volt_vault = VoltVault {
+            round_number: 2,
+            total_underlying_post_settle: 7_000,
+            total_volt_tokens_post_settle: 3_000,
+            ..VoltVault::default()
+        };
+        let round = crate::Round {
+            number: 2,
+            underlying_from_pending_deposits: 700,
+            ..crate::Round::default()
+        };
+        let round_volt_tokens = preview_deposit(&volt_vault, 700).unwrap();
+        let pending_deposit = crate::PendingDeposit {
+            initialized: true,
+            round_number: 2,
+            num_underlying_deposited: 700,
+        };
+        assert_eq!(
+            pending_deposit
+                .claimable_volt_tokens(&round, round_volt_tokens)
+                .unwrap(),
+            round_volt_tokens
+        );
+    }
+}
@@ -0,0 +1,710 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+
+use crate::error::ErrorCode;
+use crate::preflight::{
+    check, check_cancel_pending_deposit, check_cancel_pending_withdrawal,
+    check_claim_pending_deposit, check_claim_pending_withdrawal, check_phase,
+};
+use crate::share_price::{checked_add, checked_sub, mul_div_floor, SharePrice};
+use crate::{
+    ExtraVoltData, FriktionEpochInfo, InstructionKind, PendingDeposit, PendingWithdrawal,
+    PreflightVolt, Round, VoltVault,
+};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// fees are not stored on VoltVault, so they are inputs of the simulation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SimulatorFees {
+    // charged on the underlying of pending withdrawals, at end of round
+    pub withdrawal_fee_bps: u64,
+    // charged on positive round pnl, at end of round
+    pub performance_fee_bps: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserAction {
+    // underlying amount
+    Deposit(u64),
+    // volt token amount
+    Withdraw(u64),
+    ClaimPendingDeposit,
+    ClaimPendingWithdrawal,
+    CancelPendingDeposit,
+    CancelPendingWithdrawal,
+}
+
+/**
+ * One round of a simulation: user actions, applied in order after start_round, then end_round with pnl.
+ * pnl is the change in underlying of the volt's position over the round, before fees.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedRound {
+    pub actions: Vec<(Pubkey, UserAction)>,
+    pub pnl: i64,
+}
+
+// a user's wallet balances and volt PDAs
+#[derive(Clone, Default)]
+pub struct SimulatedUser {
+    pub underlying_balance: u64,
+    pub volt_token_balance: u64,
+    pub pending_deposit: PendingDeposit,
+    pub pending_withdrawal: PendingWithdrawal,
+}
+
+// a round's accounts, including the balances of its token accounts
+#[derive(Clone, Default)]
+pub struct SimulatedRound {
+    pub round: Round,
+    pub epoch_info: FriktionEpochInfo,
+    pub round_volt_tokens: u64,
+    pub round_underlying_tokens: u64,
+    pub round_ul_pending: u64,
+}
+
+/**
+ * In-memory model of a volt's accounting across rounds.
+ *  NOTES:
+ *  1. user actions are validated with preflight.rs, so they fail with the errors the program returns (transfer windows are not modeled)
+ *  2. deposits and withdrawals are always pending, instant transfers are not modeled
+ *  3. end_round applies pnl to the underlying at start_round, takes performance fees, then settles pending withdrawals
+ *     (less withdrawal fees) and pending deposits at the resulting price, like the program's end_round
+ *  4. volt tokens of pending withdrawals are burned when withdrawing, so they are added back to the supply for pricing
+ */
+pub struct VoltSimulator {
+    pub volt_vault: VoltVault,
+    pub extra_volt_data: ExtraVoltData,
+    pub fees: SimulatorFees,
+
+    // balances of the deposit pool and vault mint supply
+    pub deposit_pool: u64,
+    pub vault_mint_supply: u64,
+
+    // performance and withdrawal fees taken so far (fee_acct)
+    pub fees_collected: u64,
+
+    pub rounds: BTreeMap<u64, SimulatedRound>,
+    pub users: BTreeMap<Pubkey, SimulatedUser>,
+}
+
+fn token_account(mint: Pubkey, amount: u64) -> SplTokenAccount {
+    SplTokenAccount {
+        mint,
+        amount,
+        ..SplTokenAccount::default()
+    }
+}
+
+impl VoltSimulator {
+    /// starts from the volt's last settlement (total_underlying_post_settle and total_volt_tokens_post_settle)
+    pub fn new(volt_vault: VoltVault, extra_volt_data: ExtraVoltData, fees: SimulatorFees) -> Self {
+        VoltSimulator {
+            deposit_pool: volt_vault.total_underlying_post_settle,
+            vault_mint_supply: volt_vault.total_volt_tokens_post_settle,
+            volt_vault,
+            extra_volt_data,
+            fees,
+            fees_collected: 0,
+            rounds: BTreeMap::new(),
+            users: BTreeMap::new(),
+        }
+    }
+
+    pub fn fund_user(&mut self, user: &Pubkey, underlying_amount: u64) -> Result<()> {
+        let user = self.users.entry(*user).or_default();
+        user.underlying_balance = checked_add(user.underlying_balance, underlying_amount)?;
+        Ok(())
+    }
+
+    pub fn user(&self, user: &Pubkey) -> Option<&SimulatedUser> {
+        self.users.get(user)
+    }
+
+    pub fn round(&self, round_number: u64) -> Option<&SimulatedRound> {
+        self.rounds.get(&round_number)
+    }
+
+    fn round_mut(&mut self, round_number: u64) -> Result<&mut SimulatedRound> {
+        self.rounds
+            .get_mut(&round_number)
+            .ok_or_else(|| error!(ErrorCode::RoundHasNotStarted))
+    }
+
+    /// runs each scripted round (start_round, actions, end_round) and returns the epoch info of each
+    pub fn run(&mut self, rounds: &[ScriptedRound]) -> Result<Vec<FriktionEpochInfo>> {
+        rounds
+            .iter()
+            .map(|scripted_round| {
+                self.start_round()?;
+                for (user, action) in scripted_round.actions.iter() {
+                    self.apply(user, *action)?;
+                }
+                self.end_round(scripted_round.pnl)
+            })
+            .collect()
+    }
+
+    pub fn apply(&mut self, user: &Pubkey, action: UserAction) -> Result<()> {
+        match action {
+            UserAction::Deposit(amount) => self.deposit(user, amount),
+            UserAction::Withdraw(amount) => self.withdraw(user, amount),
+            UserAction::ClaimPendingDeposit => self.claim_pending_deposit(user),
+            UserAction::ClaimPendingWithdrawal => self.claim_pending_withdrawal(user),
+            UserAction::CancelPendingDeposit => self.cancel_pending_deposit(user),
+            UserAction::CancelPendingWithdrawal => self.cancel_pending_withdrawal(user),
+        }
+    }
+
+    ///////// round lifecycle ///////////
+
+    pub fn start_round(&mut self) -> Result<()> {
+        check(check_phase(
+            &self.volt_vault,
+            &self.extra_volt_data,
+            InstructionKind::StartRound,
+        ))?;
+
+        let round_number = checked_add(self.volt_vault.round_number, 1)?;
+        self.volt_vault.round_number = round_number;
+        self.volt_vault.round_has_started = true;
+        self.volt_vault.total_underlying_pre_enter = self.deposit_pool;
+
+        self.rounds.insert(
+            round_number,
+            SimulatedRound {
+                round: Round {
+                    number: round_number,
+                    underlying_pre_enter: self.deposit_pool,
+                    ..Round::default()
+                },
+                epoch_info: FriktionEpochInfo {
+                    number: round_number,
+                    underlying_pre_enter: self.deposit_pool,
+                    volt_token_supply: self.vault_mint_supply,
+                    ..FriktionEpochInfo::default()
+                },
+                ..SimulatedRound::default()
+            },
+        );
+        Ok(())
+    }
+
+    /// returns the epoch info of the ended round
+    pub fn end_round(&mut self, pnl: i64) -> Result<FriktionEpochInfo> {
+        if !self.volt_vault.round_has_started {
+            return err!(ErrorCode::InvalidEndRoundState);
+        }
+        let round_number = self.volt_vault.round_number;
+        let fees = self.fees;
+        let mut deposit_pool = self.deposit_pool;
+        let mut vault_mint_supply = self.vault_mint_supply;
+        let sim_round = self.round_mut(round_number)?;
+        let underlying_pre_enter = sim_round.round.underlying_pre_enter;
+
+        // pnl and performance fees
+        let underlying_post_pnl = i128::from(deposit_pool) + i128::from(pnl);
+        deposit_pool =
+            u64::try_from(underlying_post_pnl).map_err(|_| error!(ErrorCode::NumberOverflow))?;
+        let performance_fees = if pnl > 0 {
+            mul_div_floor(pnl as u64, fees.performance_fee_bps, BPS_DENOMINATOR)?
+        } else {
+            0
+        };
+        deposit_pool = checked_sub(deposit_pool, performance_fees)?;
+
+        // pending withdrawals
+        let pending_withdrawal_volt_tokens = sim_round.round.volt_tokens_from_pending_withdrawals;
+        let underlying_for_withdrawals = SharePrice {
+            total_underlying: deposit_pool,
+            total_volt_tokens: checked_add(vault_mint_supply, pending_withdrawal_volt_tokens)?,
+        }
+        .underlying_for_volt_tokens(pending_withdrawal_volt_tokens)?;
+        let withdrawal_fees = mul_div_floor(
+            underlying_for_withdrawals,
+            fees.withdrawal_fee_bps,
+            BPS_DENOMINATOR,
+        )?;
+        let pending_withdrawals = checked_sub(underlying_for_withdrawals, withdrawal_fees)?;
+        deposit_pool = checked_sub(deposit_pool, underlying_for_withdrawals)?;
+        sim_round.round_ul_pending = checked_add(sim_round.round_ul_pending, pending_withdrawals)?;
+
+        // pending deposits
+        let pending_deposits = sim_round.round.underlying_from_pending_deposits;
+        if pending_deposits > 0 {
+            let volt_tokens_for_deposits =
+                SharePrice::from_balances(vault_mint_supply, deposit_pool)
+                    .volt_tokens_for_underlying(pending_deposits)?;
+            vault_mint_supply = checked_add(vault_mint_supply, volt_tokens_for_deposits)?;
+            sim_round.round_volt_tokens =
+                checked_add(sim_round.round_volt_tokens, volt_tokens_for_deposits)?;
+            sim_round.round_underlying_tokens =
+                checked_sub(sim_round.round_underlying_tokens, pending_deposits)?;
+            deposit_pool = checked_add(deposit_pool, pending_deposits)?;
+        }
+
+        sim_round.round.underlying_post_settle = deposit_pool;
+
+        let epoch_info = &mut sim_round.epoch_info;
+        epoch_info.pnl = pnl;
+        epoch_info.pct_pnl = if underlying_pre_enter == 0 {
+            0.0
+        } else {
+            pnl as f64 / underlying_pre_enter as f64 * 100.0
+        };
+        epoch_info.performance_fees = performance_fees;
+        epoch_info.withdrawal_fees = withdrawal_fees;
+        epoch_info.pending_withdrawals = pending_withdrawals;
+        epoch_info.total_withdrawals =
+            checked_add(epoch_info.total_withdrawals, pending_withdrawals)?;
+        epoch_info.underlying_post_settle = deposit_pool;
+        epoch_info.volt_token_supply = vault_mint_supply;
+        epoch_info.vault_token_price = if vault_mint_supply == 0 {
+            1.0
+        } else {
+            deposit_pool as f64 / vault_mint_supply as f64
+        };
+        let epoch_info = epoch_info.clone();
+
+        self.deposit_pool = deposit_pool;
+        self.vault_mint_supply = vault_mint_supply;
+        self.fees_collected = checked_add(
+            self.fees_collected,
+            checked_add(performance_fees, withdrawal_fees)?,
+        )?;
+        self.volt_vault.total_underlying_post_settle = deposit_pool;
+        self.volt_vault.total_volt_tokens_post_settle = vault_mint_supply;
+        self.volt_vault.round_has_started = false;
+
+        Ok(epoch_info)
+    }
+
+    ///////// user actions ///////////
+
+    fn current_round(&self) -> Round {
+        self.rounds
+            .get(&self.volt_vault.round_number)
+            .map(|sim_round| sim_round.round.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn deposit(&mut self, user: &Pubkey, deposit_amount: u64) -> Result<()> {
+        let user_state = self.users.get(user).cloned().unwrap_or_default();
+        let round = self.current_round();
+        let deposit_pool = token_account(self.volt_vault.underlying_asset_mint, self.deposit_pool);
+        check(
+            PreflightVolt {
+                volt_vault: &self.volt_vault,
+                extra_volt_data: &self.extra_volt_data,
+                round: &round,
+                deposit_pool: &deposit_pool,
+                whitelist: None,
+            }
+            .deposit(
+                user,
+                Some(&user_state.pending_deposit),
+                &token_account(self.volt_vault.vault_mint, user_state.volt_token_balance),
+                &token_account(
+                    self.volt_vault.underlying_asset_mint,
+                    user_state.underlying_balance,
+                ),
+                deposit_amount,
+                i64::MAX,
+            ),
+        )?;
+
+        let round_number = self.volt_vault.round_number;
+        let sim_round = self.round_mut(round_number)?;
+        sim_round.round_underlying_tokens =
+            checked_add(sim_round.round_underlying_tokens, deposit_amount)?;
+        sim_round.round.underlying_from_pending_deposits = checked_add(
+            sim_round.round.underlying_from_pending_deposits,
+            deposit_amount,
+        )?;
+        let epoch_info = &mut sim_round.epoch_info;
+        epoch_info.pending_deposits = checked_add(epoch_info.pending_deposits, deposit_amount)?;
+        epoch_info.total_deposits = checked_add(epoch_info.total_deposits, deposit_amount)?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.underlying_balance = checked_sub(user_state.underlying_balance, deposit_amount)?;
+        let pending_deposit = &mut user_state.pending_deposit;
+        if pending_deposit.round_number != round_number {
+            pending_deposit.round_number = round_number;
+            pending_deposit.num_underlying_deposited = 0;
+        }
+        pending_deposit.initialized = true;
+        pending_deposit.num_underlying_deposited =
+            checked_add(pending_deposit.num_underlying_deposited, deposit_amount)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, user: &Pubkey, withdraw_amount: u64) -> Result<()> {
+        let user_state = self.users.get(user).cloned().unwrap_or_default();
+        let round = self.current_round();
+        let deposit_pool = token_account(self.volt_vault.underlying_asset_mint, self.deposit_pool);
+        check(
+            PreflightVolt {
+                volt_vault: &self.volt_vault,
+                extra_volt_data: &self.extra_volt_data,
+                round: &round,
+                deposit_pool: &deposit_pool,
+                whitelist: None,
+            }
+            .withdraw(
+                user,
+                Some(&user_state.pending_withdrawal),
+                &token_account(self.volt_vault.vault_mint, user_state.volt_token_balance),
+                &token_account(
+                    self.volt_vault.underlying_asset_mint,
+                    user_state.underlying_balance,
+                ),
+                withdraw_amount,
+                i64::MAX,
+            ),
+        )?;
+
+        // volt tokens are burned when withdrawing
+        self.vault_mint_supply = checked_sub(self.vault_mint_supply, withdraw_amount)?;
+
+        let round_number = self.volt_vault.round_number;
+        let sim_round = self.round_mut(round_number)?;
+        sim_round.round.volt_tokens_from_pending_withdrawals = checked_add(
+            sim_round.round.volt_tokens_from_pending_withdrawals,
+            withdraw_amount,
+        )?;
+        let epoch_info = &mut sim_round.epoch_info;
+        epoch_info.pending_withdrawals_volt_tokens =
+            checked_add(epoch_info.pending_withdrawals_volt_tokens, withdraw_amount)?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.volt_token_balance =
+            checked_sub(user_state.volt_token_balance, withdraw_amount)?;
+        let pending_withdrawal = &mut user_state.pending_withdrawal;
+        if pending_withdrawal.round_number != round_number {
+            pending_withdrawal.round_number = round_number;
+            pending_withdrawal.num_volt_redeemed = 0;
+        }
+        pending_withdrawal.initialized = true;
+        pending_withdrawal.num_volt_redeemed =
+            checked_add(pending_withdrawal.num_volt_redeemed, withdraw_amount)?;
+        Ok(())
+    }
+
+    pub fn claim_pending_deposit(&mut self, user: &Pubkey) -> Result<()> {
+        let pending_deposit = self
+            .users
+            .get(user)
+            .map(|user_state| user_state.pending_deposit.clone());
+        check(check_claim_pending_deposit(
+            &self.volt_vault,
+            &self.extra_volt_data,
+            pending_deposit.as_ref(),
+        ))?;
+        // checked above
+        let pending_deposit = pending_deposit.unwrap_or_default();
+
+        let sim_round = self.round_mut(pending_deposit.round_number)?;
+        let volt_tokens =
+            pending_deposit.claimable_volt_tokens(&sim_round.round, sim_round.round_volt_tokens)?;
+        sim_round.round_volt_tokens = checked_sub(sim_round.round_volt_tokens, volt_tokens)?;
+        sim_round.round.underlying_from_pending_deposits = checked_sub(
+            sim_round.round.underlying_from_pending_deposits,
+            pending_deposit.num_underlying_deposited,
+        )?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.volt_token_balance = checked_add(user_state.volt_token_balance, volt_tokens)?;
+        user_state.pending_deposit.round_number = 0;
+        user_state.pending_deposit.num_underlying_deposited = 0;
+        Ok(())
+    }
+
+    pub fn claim_pending_withdrawal(&mut self, user: &Pubkey) -> Result<()> {
+        let pending_withdrawal = self
+            .users
+            .get(user)
+            .map(|user_state| user_state.pending_withdrawal.clone());
+        check(check_claim_pending_withdrawal(
+            &self.volt_vault,
+            &self.extra_volt_data,
+            pending_withdrawal.as_ref(),
+        ))?;
+        // checked above
+        let pending_withdrawal = pending_withdrawal.unwrap_or_default();
+
+        let sim_round = self.round_mut(pending_withdrawal.round_number)?;
+        let underlying = pending_withdrawal
+            .claimable_underlying(&sim_round.round, sim_round.round_ul_pending)?;
+        sim_round.round_ul_pending = checked_sub(sim_round.round_ul_pending, underlying)?;
+        sim_round.round.volt_tokens_from_pending_withdrawals = checked_sub(
+            sim_round.round.volt_tokens_from_pending_withdrawals,
+            pending_withdrawal.num_volt_redeemed,
+        )?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.underlying_balance = checked_add(user_state.underlying_balance, underlying)?;
+        user_state.pending_withdrawal.round_number = 0;
+        user_state.pending_withdrawal.num_volt_redeemed = 0;
+        Ok(())
+    }
+
+    pub fn cancel_pending_deposit(&mut self, user: &Pubkey) -> Result<()> {
+        let pending_deposit = self
+            .users
+            .get(user)
+            .map(|user_state| user_state.pending_deposit.clone());
+        check(check_cancel_pending_deposit(
+            &self.volt_vault,
+            &self.extra_volt_data,
+            pending_deposit.as_ref(),
+        ))?;
+        // checked above
+        let num_underlying_deposited = pending_deposit.unwrap_or_default().num_underlying_deposited;
+
+        let round_number = self.volt_vault.round_number;
+        let sim_round = self.round_mut(round_number)?;
+        sim_round.round_underlying_tokens =
+            checked_sub(sim_round.round_underlying_tokens, num_underlying_deposited)?;
+        sim_round.round.underlying_from_pending_deposits = checked_sub(
+            sim_round.round.underlying_from_pending_deposits,
+            num_underlying_deposited,
+        )?;
+        let epoch_info = &mut sim_round.epoch_info;
+        epoch_info.pending_deposits =
+            checked_sub(epoch_info.pending_deposits, num_underlying_deposited)?;
+        epoch_info.total_deposits =
+            checked_sub(epoch_info.total_deposits, num_underlying_deposited)?;
+        epoch_info.canceled_deposits =
+            checked_add(epoch_info.canceled_deposits, num_underlying_deposited)?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.underlying_balance =
+            checked_add(user_state.underlying_balance, num_underlying_deposited)?;
+        user_state.pending_deposit.round_number = 0;
+        user_state.pending_deposit.num_underlying_deposited = 0;
+        Ok(())
+    }
+
+    pub fn cancel_pending_withdrawal(&mut self, user: &Pubkey) -> Result<()> {
+        let pending_withdrawal = self
+            .users
+            .get(user)
+            .map(|user_state| user_state.pending_withdrawal.clone());
+        check(check_cancel_pending_withdrawal(
+            &self.volt_vault,
+            &self.extra_volt_data,
+            pending_withdrawal.as_ref(),
+        ))?;
+        // checked above
+        let num_volt_redeemed = pending_withdrawal.unwrap_or_default().num_volt_redeemed;
+
+        // burned volt tokens are minted back
+        self.vault_mint_supply = checked_add(self.vault_mint_supply, num_volt_redeemed)?;
+
+        let round_number = self.volt_vault.round_number;
+        let sim_round = self.round_mut(round_number)?;
+        sim_round.round.volt_tokens_from_pending_withdrawals = checked_sub(
+            sim_round.round.volt_tokens_from_pending_withdrawals,
+            num_volt_redeemed,
+        )?;
+        let epoch_info = &mut sim_round.epoch_info;
+        epoch_info.pending_withdrawals_volt_tokens = checked_sub(
+            epoch_info.pending_withdrawals_volt_tokens,
+            num_volt_redeemed,
+        )?;
+        epoch_info.canceled_withdrawals =
+            checked_add(epoch_info.canceled_withdrawals, num_volt_redeemed)?;
+
+        let user_state = self.users.entry(*user).or_default();
+        user_state.volt_token_balance =
+            checked_add(user_state.volt_token_balance, num_volt_redeemed)?;
+        user_state.pending_withdrawal.round_number = 0;
+        user_state.pending_withdrawal.num_volt_redeemed = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn simulator() -> VoltSimulator {
+        VoltSimulator::new(
+            VoltVault {
+                initialized: true,
+                vault_mint: Pubkey::new_unique(),
+                underlying_asset_mint: Pubkey::new_unique(),
+                ..VoltVault::default()
+            },
+            ExtraVoltData::default(),
+            SimulatorFees {
+                withdrawal_fee_bps: 100,
+                performance_fee_bps: 1_000,
+            },
+        )
+    }
+
+    // underlying held by users, the volt's token accounts and the fee account
+    fn total_underlying(sim: &VoltSimulator) -> u64 {
+        sim.users
+            .values()
+            .map(|user| user.underlying_balance)
+            .chain(
+                sim.rounds
+                    .values()
+                    .map(|round| round.round_underlying_tokens + round.round_ul_pending),
+            )
+            .sum::<u64>()
+            + sim.deposit_pool
+            + sim.fees_collected
+    }
+
+    #[test]
+    fn scripted_rounds() {
+        let mut sim = simulator();
+        let (alice, bob) = (Pubkey::new_unique(), Pubkey::new_unique());
+        sim.fund_user(&alice, 1_000).unwrap();
+        sim.fund_user(&bob, 500).unwrap();
+
+        let epochs = sim
+            .run(&[
+                ScriptedRound {
+                    actions: vec![
+                        (alice, UserAction::Deposit(1_000)),
+                        (bob, UserAction::Deposit(500)),
+                    ],
+                    pnl: 0,
+                },
+                ScriptedRound {
+                    actions: vec![
+                        (alice, UserAction::ClaimPendingDeposit),
+                        (bob, UserAction::ClaimPendingDeposit),
+                        (alice, UserAction::Withdraw(400)),
+                    ],
+                    pnl: 300,
+                },
+                ScriptedRound {
+                    actions: vec![
+                        (alice, UserAction::ClaimPendingWithdrawal),
+                        (alice, UserAction::Deposit(118)),
+                    ],
+                    pnl: -98,
+                },
+            ])
+            .unwrap();
+
+        // deposits settle 1:1 into an empty volt
+        assert_eq!(epochs[0].pending_deposits, 1_500);
+        assert_eq!(epochs[0].underlying_post_settle, 1_500);
+        assert_eq!(epochs[0].volt_token_supply, 1_500);
+        assert_eq!(epochs[0].vault_token_price, 1.0);
+
+        // 10% of the 300 pnl is taken as performance fees, then the 400 withdrawn volt tokens are
+        // redeemed for 1770 * 400 / 1500 = 472 underlying, less a 1% withdrawal fee
+        assert_eq!(epochs[1].pnl, 300);
+        assert_eq!(epochs[1].pct_pnl, 20.0);
+        assert_eq!(epochs[1].performance_fees, 30);
+        assert_eq!(epochs[1].withdrawal_fees, 4);
+        assert_eq!(epochs[1].pending_withdrawals, 468);
+        assert_eq!(epochs[1].pending_withdrawals_volt_tokens, 400);
+        assert_eq!(epochs[1].underlying_post_settle, 1_298);
+        assert_eq!(epochs[1].volt_token_supply, 1_100);
+        assert_eq!(epochs[1].vault_token_price, 1_298.0 / 1_100.0);
+
+        // no performance fees on a loss. 118 underlying mints 118 * 1100 / 1200 = 108 volt tokens
+        assert_eq!(epochs[2].pnl, -98);
+        assert_eq!(epochs[2].pct_pnl, -98.0 / 1_298.0 * 100.0);
+        assert_eq!(epochs[2].performance_fees, 0);
+        assert_eq!(epochs[2].pending_deposits, 118);
+        assert_eq!(epochs[2].underlying_post_settle, 1_318);
+        assert_eq!(epochs[2].volt_token_supply, 1_208);
+        assert_eq!(sim.round(3).unwrap().round_volt_tokens, 108);
+
+        let alice_state = sim.user(&alice).unwrap();
+        assert_eq!(alice_state.underlying_balance, 350);
+        assert_eq!(alice_state.volt_token_balance, 600);
+        assert_eq!(alice_state.pending_deposit.num_underlying_deposited, 118);
+        assert_eq!(sim.user(&bob).unwrap().volt_token_balance, 500);
+        assert_eq!(sim.fees_collected, 34);
+        assert_eq!(sim.volt_vault.round_number, 3);
+        assert_eq!(sim.volt_vault.total_underlying_post_settle, 1_318);
+
+        // only pnl changes the underlying in the system
+        assert_eq!(total_underlying(&sim), 1_500 + 300 - 98);
+    }
+
+    #[test]
+    fn cancels_restore_balances() {
+        let mut sim = simulator();
+        let user = Pubkey::new_unique();
+        sim.fund_user(&user, 1_000).unwrap();
+        sim.start_round().unwrap();
+        sim.deposit(&user, 600).unwrap();
+        sim.cancel_pending_deposit(&user).unwrap();
+        assert_eq!(sim.user(&user).unwrap().underlying_balance, 1_000);
+        assert_eq!(sim.round(1).unwrap().epoch_info.canceled_deposits, 600);
+        assert_eq!(sim.round(1).unwrap().epoch_info.pending_deposits, 0);
+
+        sim.deposit(&user, 600).unwrap();
+        sim.end_round(0).unwrap();
+        sim.start_round().unwrap();
+        sim.claim_pending_deposit(&user).unwrap();
+        sim.withdraw(&user, 200).unwrap();
+        assert_eq!(sim.vault_mint_supply, 400);
+        sim.cancel_pending_withdrawal(&user).unwrap();
+        assert_eq!(sim.vault_mint_supply, 600);
+        assert_eq!(sim.user(&user).unwrap().volt_token_balance, 600);
+        assert_eq!(
+            sim.round(2)
+                .unwrap()
+                .round
+                .volt_tokens_from_pending_withdrawals,
+            0
+        );
+    }
+
+    #[test]
+    fn program_errors() {
+        let mut sim = simulator();
+        let user = Pubkey::new_unique();
+        sim.fund_user(&user, 100).unwrap();
+
+        assert_eq!(
+            sim.deposit(&user, 100).unwrap_err(),
+            error!(ErrorCode::RoundHasNotStarted)
+        );
+        assert_eq!(
+            sim.end_round(0).err(),
+            Some(error!(ErrorCode::InvalidEndRoundState))
+        );
+
+        sim.start_round().unwrap();
+        assert_eq!(
+            sim.deposit(&user, 101).unwrap_err(),
+            error!(ErrorCode::InsufficientCollateralForDeposit)
+        );
+        sim.deposit(&user, 100).unwrap();
+        assert_eq!(
+            sim.claim_pending_deposit(&user).unwrap_err(),
+            error!(ErrorCode::InvalidClaimPendingState)
+        );
+        assert_eq!(
+            sim.start_round().unwrap_err(),
+            error!(ErrorCode::InvalidStartRoundState)
+        );
+
+        // a scripted action failing stops the run
+        sim.end_round(0).unwrap();
+        assert_eq!(
+            sim.run(&[ScriptedRound {
+                actions: vec![(user, UserAction::Withdraw(1))],
+                pnl: 0,
+            }])
+            .err(),
+            Some(error!(ErrorCode::InsufficientVaultTokens))
+        );
+    }
+}
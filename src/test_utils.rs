@@ -0,0 +1,275 @@
+// helpers for unit tests that need AccountInfos. everything is leaked, so the infos are 'static
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+use std::sync::Once;
+
+use anchor_lang::prelude::*;
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::AccountSerialize;
+use anchor_spl::token::spl_token;
+
+pub fn account_info(
+    key: Pubkey,
+    owner: Pubkey,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+) -> AccountInfo<'static> {
+    AccountInfo::new(
+        Box::leak(Box::new(key)),
+        is_signer,
+        is_writable,
+        Box::leak(Box::new(1_000_000_000)),
+        Box::leak(data.into_boxed_slice()),
+        Box::leak(Box::new(owner)),
+        false,
+        0,
+    )
+}
+
+pub fn program_info(program_id: Pubkey) -> AccountInfo<'static> {
+    let mut info = account_info(program_id, Pubkey::default(), vec![], false, false);
+    info.executable = true;
+    info
+}
+
+// bincode layout of Rent, for Sysvar<Rent> accounts
+#[cfg(feature = "mock-program")]
+pub fn rent_sysvar_account() -> AccountInfo<'static> {
+    let rent = Rent::default();
+    let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+    data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+    data.push(rent.burn_percent);
+    account_info(
+        anchor_lang::solana_program::sysvar::rent::ID,
+        anchor_lang::solana_program::sysvar::ID,
+        data,
+        false,
+        false,
+    )
+}
+
+pub fn serialize_account<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+pub fn program_account<T: AccountSerialize>(
+    key: Pubkey,
+    program_id: Pubkey,
+    account: &T,
+) -> AccountInfo<'static> {
+    account_info(key, program_id, serialize_account(account), false, true)
+}
+
+pub fn mint_account(key: Pubkey, authority: Pubkey, supply: u64) -> AccountInfo<'static> {
+    let mint = spl_token::state::Mint {
+        mint_authority: Some(authority).into(),
+        supply,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: None.into(),
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    mint.pack_into_slice(&mut data);
+    account_info(key, spl_token::ID, data, false, true)
+}
+
+pub fn token_account(
+    key: Pubkey,
+    mint: Pubkey,
+    owner: Pubkey,
+    amount: u64,
+) -> AccountInfo<'static> {
+    let token_account = spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    token_account.pack_into_slice(&mut data);
+    account_info(key, spl_token::ID, data, false, true)
+}
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+pub fn set_token_amount(account: &AccountInfo, amount: u64) {
+    let mut token_account = spl_token::state::Account::unpack(&account.data.borrow()).unwrap();
+    token_account.amount = amount;
+    token_account.pack_into_slice(&mut account.data.borrow_mut());
+}
+
+#[cfg(feature = "mock-program")]
+pub fn token_amount(account: &AccountInfo) -> u64 {
+    spl_token::state::Account::unpack(&account.data.borrow())
+        .unwrap()
+        .amount
+}
+
+///////// syscalls ///////////
+
+// off-chain stand-ins for the syscalls handlers make: default Rent and Clock (unix_timestamp 0),
+// CPIs into the token program, which are run in process, and the system instructions anchor's init
+// makes. CPIs into any other program are recorded (see take_invoked) and succeed without running anything
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+struct TestSyscallStubs;
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+#[cfg_attr(not(feature = "cpi"), allow(dead_code))]
+pub struct Invoked {
+    pub instruction: Instruction,
+    pub signer_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+thread_local! {
+    static INVOKED: std::cell::RefCell<Vec<Invoked>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// CPIs into programs other than the token program made on this thread since the last call
+#[cfg(feature = "cpi")]
+pub fn take_invoked() -> Vec<Invoked> {
+    INVOKED.with(|invoked| invoked.take())
+}
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+impl SyscallStubs for TestSyscallStubs {
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = Clock::default() };
+        0
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        0
+    }
+
+    // signer seeds are trusted, signers are taken from the instruction
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id == anchor_lang::system_program::ID {
+            return process_system_instruction(instruction, account_infos);
+        }
+        if instruction.program_id != spl_token::ID {
+            let signer_seeds = signers_seeds
+                .iter()
+                .map(|seeds| seeds.iter().map(|seed| seed.to_vec()).collect())
+                .collect();
+            INVOKED.with(|invoked| {
+                invoked.borrow_mut().push(Invoked {
+                    instruction: instruction.clone(),
+                    signer_seeds,
+                })
+            });
+            return Ok(());
+        }
+        let accounts = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                let mut info = find_account(account_infos, &meta.pubkey)?.clone();
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                Ok(info)
+            })
+            .collect::<std::result::Result<Vec<_>, ProgramError>>()?;
+        spl_token::processor::Processor::process(&spl_token::ID, &accounts, &instruction.data)
+    }
+}
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+fn find_account<'a, 'info>(
+    account_infos: &'a [AccountInfo<'info>],
+    key: &Pubkey,
+) -> std::result::Result<&'a AccountInfo<'info>, ProgramError> {
+    account_infos
+        .iter()
+        .find(|info| info.key == key)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+// CreateAccount, Assign, Transfer and Allocate, w/o the checks of the system program. allocated data replaces
+// the account's data, which the AccountInfos of the outer instruction share
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+fn process_system_instruction(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+) -> ProgramResult {
+    let data = &instruction.data;
+    let u64_at = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let pubkey_at = |offset: usize| {
+        data.get(offset..offset + 32)
+            .map(Pubkey::new)
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+    let account = |index: usize| {
+        let meta = instruction
+            .accounts
+            .get(index)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        find_account(account_infos, &meta.pubkey)
+    };
+    let transfer = |lamports: u64| -> ProgramResult {
+        let (from, to) = (account(0)?, account(1)?);
+        **from.try_borrow_mut_lamports()? = from
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        **to.try_borrow_mut_lamports()? += lamports;
+        Ok(())
+    };
+    let allocate = |info: &AccountInfo, space: u64| -> ProgramResult {
+        *info.try_borrow_mut_data()? = Box::leak(vec![0; space as usize].into_boxed_slice());
+        Ok(())
+    };
+
+    match u32::from_le_bytes(
+        data.get(..4)
+            .ok_or(ProgramError::InvalidInstructionData)?
+            .try_into()
+            .unwrap(),
+    ) {
+        // CreateAccount { lamports, space, owner }
+        0 => {
+            transfer(u64_at(4)?)?;
+            allocate(account(1)?, u64_at(12)?)?;
+            account(1)?.assign(&pubkey_at(20)?);
+            Ok(())
+        }
+        // Assign { owner }
+        1 => {
+            account(0)?.assign(&pubkey_at(4)?);
+            Ok(())
+        }
+        // Transfer { lamports }
+        2 => transfer(u64_at(4)?),
+        // Allocate { space }
+        8 => allocate(account(0)?, u64_at(4)?),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+#[cfg(any(feature = "cpi", feature = "mock-program"))]
+pub fn set_test_syscall_stubs() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        set_syscall_stubs(Box::new(TestSyscallStubs));
+    });
+}
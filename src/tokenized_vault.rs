@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::*;
+
+use crate::preflight::{check_phase, check_transfer_window, check_whitelist};
+use crate::{
+    ExtraVoltData, InstructionKind, PendingDeposit, Round, SharePrice, VoltVault, Whitelist,
+};
+
+/**
+ * ERC-4626 style interface over a vault that takes deposits of one asset mint and issues one share mint.
+ * Amounts are in native units of the respective mint.
+ */
+pub trait TokenizedVault {
+    fn asset_mint(&self) -> Pubkey;
+
+    fn share_mint(&self) -> Pubkey;
+
+    fn total_assets(&self) -> u64;
+
+    fn convert_to_shares(&self, assets: u64) -> Result<u64>;
+
+    fn convert_to_assets(&self, shares: u64) -> Result<u64>;
+
+    // max assets receiver may deposit right now, 0 if deposits are not possible
+    fn max_deposit(&self, receiver: &Pubkey) -> u64;
+
+    fn preview_deposit(&self, assets: u64) -> Result<u64>;
+
+    // max assets owner may withdraw right now, 0 if withdrawals are not possible
+    fn max_withdraw(&self, owner: &Pubkey) -> u64;
+
+    fn preview_redeem(&self, shares: u64) -> Result<u64>;
+}
+
+#[derive(Clone, Default)]
+pub struct VoltPosition {
+    pub pending_deposit: Option<PendingDeposit>,
+
+    // balance of the user's token account w/ mint == vault mint
+    pub volt_token_balance: u64,
+}
+
+/**
+ * Point-in-time view of a volt (and optionally some of its users) implementing TokenizedVault.
+ *  NOTES:
+ *  1. deposits and withdrawals are pending until the round ends, so previews are priced at the last settlement (see SharePrice::post_settle)
+ *  2. max_deposit/max_withdraw are 0 outside of the deposit window, while deposits are turned off, or for non-whitelisted users
+ *  3. users missing from positions are treated as having no pending deposit and no volt tokens
+ */
+#[derive(Clone, Default)]
+pub struct VoltSnapshot {
+    pub volt_vault: VoltVault,
+    pub extra_volt_data: ExtraVoltData,
+
+    // Round account for volt_vault.round_number
+    pub round: Round,
+
+    pub deposit_pool_balance: u64,
+
+    // required if extra_volt_data.is_whitelisted
+    pub whitelist: Option<Whitelist>,
+
+    pub unix_timestamp: i64,
+
+    pub positions: BTreeMap<Pubkey, VoltPosition>,
+}
+
+impl VoltSnapshot {
+    fn share_price(&self) -> SharePrice {
+        SharePrice::post_settle(&self.volt_vault)
+    }
+
+    fn position(&self, user: &Pubkey) -> VoltPosition {
+        self.positions.get(user).cloned().unwrap_or_default()
+    }
+
+    fn accepts(&self, ix: InstructionKind, user: &Pubkey) -> bool {
+        check_phase(&self.volt_vault, &self.extra_volt_data, ix).is_ok()
+            && check_transfer_window(
+                &self.volt_vault,
+                &self.extra_volt_data,
+                ix,
+                self.unix_timestamp,
+            )
+            .is_ok()
+            && check_whitelist(&self.extra_volt_data, self.whitelist.as_ref(), user).is_ok()
+    }
+}
+
+impl TokenizedVault for VoltSnapshot {
+    fn asset_mint(&self) -> Pubkey {
+        if self.extra_volt_data.deposit_mint != Pubkey::default() {
+            self.extra_volt_data.deposit_mint
+        } else {
+            self.volt_vault.underlying_asset_mint
+        }
+    }
+
+    fn share_mint(&self) -> Pubkey {
+        self.volt_vault.vault_mint
+    }
+
+    fn total_assets(&self) -> u64 {
+        self.volt_vault.total_underlying_post_settle
+    }
+
+    fn convert_to_shares(&self, assets: u64) -> Result<u64> {
+        self.share_price().volt_tokens_for_underlying(assets)
+    }
+
+    fn convert_to_assets(&self, shares: u64) -> Result<u64> {
+        self.share_price().underlying_for_volt_tokens(shares)
+    }
+
+    fn max_deposit(&self, receiver: &Pubkey) -> u64 {
+        if !self.accepts(InstructionKind::DepositWithClaim, receiver) {
+            return 0;
+        }
+
+        let mut max_deposit = u64::MAX;
+        if self.volt_vault.capacity > 0 {
+            let vault_total = self
+                .deposit_pool_balance
+                .saturating_add(self.round.underlying_from_pending_deposits);
+            max_deposit = max_deposit.min(self.volt_vault.capacity.saturating_sub(vault_total));
+        }
+        if self.volt_vault.individual_capacity > 0 {
+            let pending_this_round = match self.position(receiver).pending_deposit {
+                Some(pd) if pd.round_number == self.volt_vault.round_number => {
+                    pd.num_underlying_deposited
+                }
+                _ => 0,
+            };
+            max_deposit = max_deposit.min(
+                self.volt_vault
+                    .individual_capacity
+                    .saturating_sub(pending_this_round),
+            );
+        }
+        max_deposit
+    }
+
+    fn preview_deposit(&self, assets: u64) -> Result<u64> {
+        self.convert_to_shares(assets)
+    }
+
+    fn max_withdraw(&self, owner: &Pubkey) -> u64 {
+        if !self.accepts(InstructionKind::WithdrawWithClaim, owner) {
+            return 0;
+        }
+        self.convert_to_assets(self.position(owner).volt_token_balance)
+            .unwrap_or(0)
+    }
+
+    fn preview_redeem(&self, shares: u64) -> Result<u64> {
+        self.convert_to_assets(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> VoltSnapshot {
+        VoltSnapshot {
+            volt_vault: VoltVault {
+                initialized: true,
+                round_has_started: true,
+                round_number: 2,
+                underlying_asset_mint: Pubkey::new_unique(),
+                vault_mint: Pubkey::new_unique(),
+                total_underlying_post_settle: 2_000,
+                total_volt_tokens_post_settle: 1_000,
+                ..VoltVault::default()
+            },
+            ..VoltSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        let snapshot = snapshot();
+        assert_eq!(snapshot.total_assets(), 2_000);
+        assert_eq!(snapshot.convert_to_shares(3).unwrap(), 1);
+        assert_eq!(snapshot.preview_deposit(4).unwrap(), 2);
+        assert_eq!(snapshot.convert_to_assets(3).unwrap(), 6);
+        assert_eq!(snapshot.preview_redeem(3).unwrap(), 6);
+        assert!(snapshot.preview_redeem(1_001).is_err());
+    }
+
+    #[test]
+    fn asset_mint_prefers_deposit_mint() {
+        let mut snapshot = snapshot();
+        assert_eq!(
+            snapshot.asset_mint(),
+            snapshot.volt_vault.underlying_asset_mint
+        );
+        assert_eq!(snapshot.share_mint(), snapshot.volt_vault.vault_mint);
+
+        snapshot.extra_volt_data.deposit_mint = Pubkey::new_unique();
+        assert_eq!(snapshot.asset_mint(), snapshot.extra_volt_data.deposit_mint);
+    }
+
+    #[test]
+    fn max_deposit_capacities() {
+        let user = Pubkey::new_unique();
+        let mut snapshot = snapshot();
+        assert_eq!(snapshot.max_deposit(&user), u64::MAX);
+
+        snapshot.volt_vault.capacity = 1_000;
+        snapshot.deposit_pool_balance = 600;
+        snapshot.round.underlying_from_pending_deposits = 100;
+        assert_eq!(snapshot.max_deposit(&user), 300);
+
+        snapshot.volt_vault.individual_capacity = 250;
+        snapshot.positions.insert(
+            user,
+            VoltPosition {
+                pending_deposit: Some(PendingDeposit {
+                    initialized: true,
+                    round_number: 2,
+                    num_underlying_deposited: 50,
+                }),
+                volt_token_balance: 0,
+            },
+        );
+        assert_eq!(snapshot.max_deposit(&user), 200);
+
+        // a pending deposit from an older round doesn't count against this round
+        snapshot.volt_vault.round_number = 3;
+        assert_eq!(snapshot.max_deposit(&user), 250);
+
+        snapshot.deposit_pool_balance = 2_000;
+        assert_eq!(snapshot.max_deposit(&user), 0);
+    }
+
+    #[test]
+    fn closed_volt() {
+        let user = Pubkey::new_unique();
+        let mut snapshot = snapshot();
+        snapshot.positions.insert(
+            user,
+            VoltPosition {
+                pending_deposit: None,
+                volt_token_balance: 10,
+            },
+        );
+        assert_eq!(snapshot.max_withdraw(&user), 20);
+        assert_eq!(snapshot.max_withdraw(&Pubkey::new_unique()), 0);
+
+        // outside of the transfer window
+        snapshot.volt_vault.start_transfer_time = 100;
+        assert_eq!(snapshot.max_deposit(&user), 0);
+        assert_eq!(snapshot.max_withdraw(&user), 0);
+        snapshot.unix_timestamp = 100;
+        assert_eq!(snapshot.max_withdraw(&user), 20);
+
+        snapshot.extra_volt_data.turn_off_deposits_and_withdrawals = true;
+        assert_eq!(snapshot.max_deposit(&user), 0);
+        snapshot.extra_volt_data.turn_off_deposits_and_withdrawals = false;
+
+        snapshot.extra_volt_data.is_whitelisted = true;
+        snapshot.whitelist = Some(Whitelist::default());
+        assert_eq!(snapshot.max_deposit(&user), 0);
+        snapshot.whitelist.as_mut().unwrap().addresses.push(user);
+        assert_eq!(snapshot.max_deposit(&user), u64::MAX);
+
+        snapshot.volt_vault.round_has_started = false;
+        assert_eq!(snapshot.max_deposit(&user), 0);
+    }
+}
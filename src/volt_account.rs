@@ -0,0 +1,417 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::error::ErrorCode;
+use crate::layout::{HasLayout, DISCRIMINATOR_LEN};
+use crate::{
+    EntropyMetadata, EntropyRound, ExtraVoltData, FriktionEpochInfo, PendingDeposit,
+    PendingWithdrawal, PrincipalProtectionVaultV1, Round, UlOpenOrdersMetadata, VoltVault,
+    Whitelist,
+};
+
+/**
+ * Any account owned by the volt program, decoded by its 8 byte anchor discriminator.
+ */
+pub enum VoltAccount {
+    VoltVault(Box<VoltVault>),
+    ExtraVoltData(Box<ExtraVoltData>),
+    Round(Round),
+    FriktionEpochInfo(Box<FriktionEpochInfo>),
+    PendingDeposit(PendingDeposit),
+    PendingWithdrawal(PendingWithdrawal),
+    Whitelist(Whitelist),
+    EntropyRound(Box<EntropyRound>),
+    EntropyMetadata(Box<EntropyMetadata>),
+    UlOpenOrdersMetadata(UlOpenOrdersMetadata),
+    PrincipalProtectionVaultV1(Box<PrincipalProtectionVaultV1>),
+}
+
+/// (account name, discriminator) of every account type owned by the volt program
+pub fn volt_account_discriminators() -> [(&'static str, [u8; 8]); 11] {
+    [
+        ("VoltVault", VoltVault::discriminator()),
+        ("ExtraVoltData", ExtraVoltData::discriminator()),
+        ("Round", Round::discriminator()),
+        ("FriktionEpochInfo", FriktionEpochInfo::discriminator()),
+        ("PendingDeposit", PendingDeposit::discriminator()),
+        ("PendingWithdrawal", PendingWithdrawal::discriminator()),
+        ("Whitelist", Whitelist::discriminator()),
+        ("EntropyRound", EntropyRound::discriminator()),
+        ("EntropyMetadata", EntropyMetadata::discriminator()),
+        (
+            "UlOpenOrdersMetadata",
+            UlOpenOrdersMetadata::discriminator(),
+        ),
+        (
+            "PrincipalProtectionVaultV1",
+            PrincipalProtectionVaultV1::discriminator(),
+        ),
+    ]
+}
+
+pub fn volt_account_name(discriminator: &[u8; 8]) -> Option<&'static str> {
+    volt_account_discriminators()
+        .iter()
+        .find(|(_, d)| d == discriminator)
+        .map(|(name, _)| *name)
+}
+
+fn decode<T: AccountDeserialize>(name: &str, data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..]).map_err(|e| e.with_account_name(name))
+}
+
+impl VoltAccount {
+    /// like try_from_bytes, but VoltVault, ExtraVoltData and FriktionEpochInfo may be cut short at a field boundary, see try_deserialize_prefix
+    pub fn try_from_bytes_prefix(data: &[u8]) -> Result<(Self, FieldCoverage)> {
+        let mut discriminator = [0u8; 8];
+        if let Some(prefix) = data.get(..8) {
+            discriminator.copy_from_slice(prefix);
+        }
+
+        Ok(if discriminator == VoltVault::discriminator() {
+            let decoded = try_deserialize_prefix::<VoltVault>(data)?;
+            (
+                VoltAccount::VoltVault(Box::new(decoded.account)),
+                decoded.coverage,
+            )
+        } else if discriminator == ExtraVoltData::discriminator() {
+            let decoded = try_deserialize_prefix::<ExtraVoltData>(data)?;
+            (
+                VoltAccount::ExtraVoltData(Box::new(decoded.account)),
+                decoded.coverage,
+            )
+        } else if discriminator == FriktionEpochInfo::discriminator() {
+            let decoded = try_deserialize_prefix::<FriktionEpochInfo>(data)?;
+            (
+                VoltAccount::FriktionEpochInfo(Box::new(decoded.account)),
+                decoded.coverage,
+            )
+        } else {
+            (Self::try_from_bytes(data)?, FieldCoverage::Complete)
+        })
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 {
+            return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound);
+        }
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&data[..8]);
+
+        Ok(if discriminator == VoltVault::discriminator() {
+            VoltAccount::VoltVault(Box::new(decode("VoltVault", data)?))
+        } else if discriminator == ExtraVoltData::discriminator() {
+            VoltAccount::ExtraVoltData(Box::new(decode("ExtraVoltData", data)?))
+        } else if discriminator == Round::discriminator() {
+            VoltAccount::Round(decode("Round", data)?)
+        } else if discriminator == FriktionEpochInfo::discriminator() {
+            VoltAccount::FriktionEpochInfo(Box::new(decode("FriktionEpochInfo", data)?))
+        } else if discriminator == PendingDeposit::discriminator() {
+            VoltAccount::PendingDeposit(decode("PendingDeposit", data)?)
+        } else if discriminator == PendingWithdrawal::discriminator() {
+            VoltAccount::PendingWithdrawal(decode("PendingWithdrawal", data)?)
+        } else if discriminator == Whitelist::discriminator() {
+            VoltAccount::Whitelist(decode("Whitelist", data)?)
+        } else if discriminator == EntropyRound::discriminator() {
+            VoltAccount::EntropyRound(Box::new(decode("EntropyRound", data)?))
+        } else if discriminator == EntropyMetadata::discriminator() {
+            VoltAccount::EntropyMetadata(Box::new(decode("EntropyMetadata", data)?))
+        } else if discriminator == UlOpenOrdersMetadata::discriminator() {
+            VoltAccount::UlOpenOrdersMetadata(decode("UlOpenOrdersMetadata", data)?)
+        } else if discriminator == PrincipalProtectionVaultV1::discriminator() {
+            VoltAccount::PrincipalProtectionVaultV1(Box::new(decode(
+                "PrincipalProtectionVaultV1",
+                data,
+            )?))
+        } else {
+            return Err(error!(ErrorCode::DiscriminatorDoesNotMatch).with_values((
+                format!("{:?}", discriminator),
+                "discriminator of a volt program account",
+            )));
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoltAccount::VoltVault(_) => "VoltVault",
+            VoltAccount::ExtraVoltData(_) => "ExtraVoltData",
+            VoltAccount::Round(_) => "Round",
+            VoltAccount::FriktionEpochInfo(_) => "FriktionEpochInfo",
+            VoltAccount::PendingDeposit(_) => "PendingDeposit",
+            VoltAccount::PendingWithdrawal(_) => "PendingWithdrawal",
+            VoltAccount::Whitelist(_) => "Whitelist",
+            VoltAccount::EntropyRound(_) => "EntropyRound",
+            VoltAccount::EntropyMetadata(_) => "EntropyMetadata",
+            VoltAccount::UlOpenOrdersMetadata(_) => "UlOpenOrdersMetadata",
+            VoltAccount::PrincipalProtectionVaultV1(_) => "PrincipalProtectionVaultV1",
+        }
+    }
+}
+
+///////// decoding of truncated accounts ///////////
+
+/**
+ * Fields of the current layout covered by the decoded data.
+ *  NOTES:
+ *  1. only data that is a prefix of the current layout, cut at a field boundary, is supported (e.g an account allocated before fields were appended to it)
+ *  2. this is not a registry of the program's historical layouts. data laid out differently (removed/reordered/resized fields) isn't detected,
+ *     it decodes into the wrong fields or errors if it doesn't end at a field boundary
+ *  3. no schema version is reported: the account lengths of past program versions aren't published, so coverage is the only thing
+ *     the data tells us (see "Not included" in the README)
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldCoverage {
+    // data covers every field of the current layout. trailing bytes (e.g space allocated beyond LEN) are ignored
+    Complete,
+    // data ends after fields_present fields of the current layout, the remaining fields were defaulted to zero
+    Prefix {
+        fields_present: usize,
+        last_field: Option<&'static str>,
+    },
+}
+
+impl FieldCoverage {
+    pub fn is_complete(&self) -> bool {
+        matches!(self, FieldCoverage::Complete)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PrefixDecoded<T> {
+    pub account: T,
+    pub coverage: FieldCoverage,
+}
+
+/// decodes T from account data holding all fields of the current layout or a prefix of them. errors if the data ends in the middle of a field
+pub fn try_deserialize_prefix<T>(data: &[u8]) -> Result<PrefixDecoded<T>>
+where
+    T: AccountDeserialize + Discriminator + HasLayout,
+{
+    let layout = T::LAYOUT;
+    if data.len() < DISCRIMINATOR_LEN {
+        return err!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound);
+    }
+    if data[..DISCRIMINATOR_LEN] != T::discriminator() {
+        return Err(
+            error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch)
+                .with_account_name(layout.name),
+        );
+    }
+
+    let current_len = DISCRIMINATOR_LEN + layout.size;
+    if data.len() >= current_len {
+        let account = decode(layout.name, data)?;
+        return Ok(PrefixDecoded {
+            account,
+            coverage: FieldCoverage::Complete,
+        });
+    }
+
+    let fields_present = layout
+        .fields
+        .iter()
+        .take_while(|field| field.offset + field.size <= data.len())
+        .count();
+    let fields_end = layout.fields[..fields_present]
+        .last()
+        .map_or(DISCRIMINATOR_LEN, |field| field.offset + field.size);
+    if data.len() != fields_end {
+        return Err(
+            error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+                .with_account_name(layout.name)
+                .with_values((data.len(), "a field boundary of the layout")),
+        );
+    }
+
+    // missing trailing fields decode as zero, which is their default
+    let mut padded = data.to_vec();
+    padded.resize(current_len, 0);
+    let account = decode(layout.name, &padded)?;
+    Ok(PrefixDecoded {
+        account,
+        coverage: FieldCoverage::Prefix {
+            fields_present,
+            last_field: layout.fields[..fields_present]
+                .last()
+                .map(|field| field.name),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::serialize_account;
+
+    #[test]
+    fn discriminators_are_unique() {
+        let discriminators = volt_account_discriminators();
+        for (i, (name, discriminator)) in discriminators.iter().enumerate() {
+            assert_eq!(volt_account_name(discriminator), Some(*name));
+            assert!(discriminators[i + 1..]
+                .iter()
+                .all(|(_, other)| other != discriminator));
+        }
+        assert_eq!(volt_account_name(&[0; 8]), None);
+    }
+
+    #[test]
+    fn decodes_by_discriminator() {
+        let round = Round {
+            number: 5,
+            underlying_from_pending_deposits: 6,
+            volt_tokens_from_pending_withdrawals: 7,
+            ..Round::default()
+        };
+        match VoltAccount::try_from_bytes(&serialize_account(&round)).unwrap() {
+            VoltAccount::Round(decoded) => {
+                assert_eq!(decoded.number, 5);
+                assert_eq!(decoded.underlying_from_pending_deposits, 6);
+                assert_eq!(decoded.volt_tokens_from_pending_withdrawals, 7);
+            }
+            other => panic!("decoded as {}", other.name()),
+        }
+
+        let volt_vault = VoltVault {
+            round_number: 9,
+            ..VoltVault::default()
+        };
+        let decoded = VoltAccount::try_from_bytes(&serialize_account(&volt_vault)).unwrap();
+        assert_eq!(decoded.name(), "VoltVault");
+        assert!(matches!(decoded, VoltAccount::VoltVault(v) if v.round_number == 9));
+
+        let whitelist = Whitelist {
+            admin: Pubkey::new_unique(),
+            addresses: vec![Pubkey::new_unique()],
+        };
+        let decoded = VoltAccount::try_from_bytes(&serialize_account(&whitelist)).unwrap();
+        assert!(matches!(decoded, VoltAccount::Whitelist(w) if w.addresses == whitelist.addresses));
+    }
+
+    #[test]
+    fn unknown_and_short_data() {
+        assert_eq!(
+            VoltAccount::try_from_bytes(&[1; 8]).err().unwrap(),
+            error!(ErrorCode::DiscriminatorDoesNotMatch)
+        );
+        assert_eq!(
+            VoltAccount::try_from_bytes(&[1; 7]).err().unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)
+        );
+
+        // known discriminator, not enough data
+        let data = serialize_account(&Round::default());
+        assert_eq!(
+            VoltAccount::try_from_bytes(&data[..12]).err().unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+        );
+    }
+
+    // volt vault allocated before whitelist_token_mint, permissioned_market_premium_* and capacity were appended
+    fn older_volt_vault_data() -> (VoltVault, Vec<u8>) {
+        let volt_vault = VoltVault {
+            admin_key: Pubkey::new_unique(),
+            round_number: 4,
+            vault_mint: Pubkey::new_unique(),
+            serum_client_order_id: 11,
+            whitelist_token_mint: Pubkey::new_unique(),
+            capacity: 1_000,
+            ..VoltVault::default()
+        };
+        let mut data = serialize_account(&volt_vault);
+        data.truncate(8 + 635);
+        (volt_vault, data)
+    }
+
+    #[test]
+    fn decodes_prefix_at_field_boundary() {
+        let (volt_vault, data) = older_volt_vault_data();
+        let decoded = try_deserialize_prefix::<VoltVault>(&data).unwrap();
+        assert_eq!(
+            decoded.coverage,
+            FieldCoverage::Prefix {
+                fields_present: VoltVault::LAYOUT.fields.len() - 4,
+                last_field: Some("serum_client_order_id"),
+            }
+        );
+        assert_eq!(decoded.account.admin_key, volt_vault.admin_key);
+        assert_eq!(decoded.account.round_number, 4);
+        assert_eq!(decoded.account.vault_mint, volt_vault.vault_mint);
+        assert_eq!(decoded.account.serum_client_order_id, 11);
+        // missing fields are zero
+        assert_eq!(decoded.account.whitelist_token_mint, Pubkey::default());
+        assert_eq!(decoded.account.capacity, 0);
+
+        // only the discriminator
+        let decoded = try_deserialize_prefix::<VoltVault>(&data[..8]).unwrap();
+        assert_eq!(
+            decoded.coverage,
+            FieldCoverage::Prefix {
+                fields_present: 0,
+                last_field: None,
+            }
+        );
+
+        let (account, coverage) = VoltAccount::try_from_bytes_prefix(&data).unwrap();
+        assert!(!coverage.is_complete());
+        assert!(matches!(account, VoltAccount::VoltVault(v) if v.round_number == 4));
+        // the strict decoder rejects it
+        assert!(VoltAccount::try_from_bytes(&data).is_err());
+    }
+
+    #[test]
+    fn complete_data_ignores_slack() {
+        let extra_volt_data = ExtraVoltData {
+            is_for_dao: true,
+            have_taken_performance_fees: true,
+            ..ExtraVoltData::default()
+        };
+        let mut data = serialize_account(&extra_volt_data);
+        assert_eq!(data.len(), 8 + ExtraVoltData::LEN);
+        data.extend_from_slice(&[7; 64]);
+        let decoded = try_deserialize_prefix::<ExtraVoltData>(&data).unwrap();
+        assert!(decoded.coverage.is_complete());
+        assert!(decoded.account.is_for_dao);
+        assert!(decoded.account.have_taken_performance_fees);
+
+        // accounts w/o a layout are decoded strictly
+        let data = serialize_account(&Round::default());
+        let (account, coverage) = VoltAccount::try_from_bytes_prefix(&data).unwrap();
+        assert_eq!(account.name(), "Round");
+        assert!(coverage.is_complete());
+        assert!(VoltAccount::try_from_bytes_prefix(&data[..12]).is_err());
+    }
+
+    #[test]
+    fn rejects_cut_mid_field() {
+        let (_, data) = older_volt_vault_data();
+        // slack after serum_client_order_id that doesn't fill whitelist_token_mint
+        let mut data = data;
+        data.extend_from_slice(&[0; 16]);
+        assert_eq!(
+            try_deserialize_prefix::<VoltVault>(&data).err().unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+        );
+        assert_eq!(
+            try_deserialize_prefix::<VoltVault>(&data[..8 + 100])
+                .err()
+                .unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize)
+        );
+
+        let mut data = serialize_account(&FriktionEpochInfo::default());
+        data[0] ^= 1;
+        assert_eq!(
+            try_deserialize_prefix::<FriktionEpochInfo>(&data)
+                .err()
+                .unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch)
+        );
+        assert_eq!(
+            try_deserialize_prefix::<FriktionEpochInfo>(&data[..4])
+                .err()
+                .unwrap(),
+            error!(anchor_lang::error::ErrorCode::AccountDiscriminatorNotFound)
+        );
+    }
+}
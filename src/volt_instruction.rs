@@ -0,0 +1,526 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::InstructionKind;
+
+/**
+ * Any instruction of the volt program, decoded by its 8 byte anchor sighash ("global:<name>").
+ * Mirrors the handlers in the volt_abi program module, including their arguments.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum VoltInstruction {
+    // user
+    Deposit { deposit_amount: u64 },
+    Withdraw { amount: u64 },
+    DepositWithClaim { amount: u64, do_transfer: bool },
+    WithdrawWithClaim { amount: u64 },
+    ClaimPendingDeposit,
+    ClaimPendingWithdrawal,
+    CancelPendingDeposit,
+    CancelPendingWithdrawal,
+}
+
+/**
+ * A decoded instruction along with its accounts, labeled by field name of the matching Accounts struct.
+ *  NOTES:
+ *  1. accounts past the end of the Accounts struct (e.g. entropy or principal protection accounts) are left unlabeled in remaining_accounts
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecodedVoltInstruction {
+    pub instruction: VoltInstruction,
+    pub accounts: Vec<(&'static str, Pubkey)>,
+    pub remaining_accounts: Vec<Pubkey>,
+}
+
+///////// account names, in the order of the fields of the Accounts structs in contexts.rs ///////////
+
+const DEPOSIT_ACCOUNTS: &[&str] = &[
+    "payer_authority",
+    "non_payer_authority",
+    "authority_check",
+    "vault_mint",
+    "volt_vault",
+    "vault_authority",
+    "extra_volt_data",
+    "whitelist",
+    "deposit_pool",
+    "writer_token_pool",
+    "user_vault_tokens",
+    "user_ul_tokens",
+    "round_info",
+    "round_volt_tokens",
+    "round_underlying_tokens",
+    "pending_deposit_info",
+    "epoch_info",
+    "system_program",
+    "token_program",
+];
+
+const WITHDRAW_ACCOUNTS: &[&str] = &[
+    "payer_authority",
+    "non_payer_authority",
+    "authority_check",
+    "vault_mint",
+    "volt_vault",
+    "extra_volt_data",
+    "vault_authority",
+    "whitelist",
+    "deposit_pool",
+    "underlying_token_destination",
+    "vault_token_source",
+    "round_info",
+    "round_underlying_tokens",
+    "pending_withdrawal_info",
+    "epoch_info",
+    "fee_acct",
+    "system_program",
+    "token_program",
+    "rent",
+];
+
+const DEPOSIT_WITH_CLAIM_ACCOUNTS: &[&str] = &[
+    "payer_authority",
+    "non_payer_authority",
+    "sol_transfer_authority",
+    "authority_check",
+    "vault_mint",
+    "volt_vault",
+    "vault_authority",
+    "extra_volt_data",
+    "deposit_pool",
+    "writer_token_pool",
+    "vault_token_destination",
+    "underlying_token_source",
+    "round_info",
+    "round_underlying_tokens",
+    "pending_deposit_info",
+    "pending_deposit_round_info",
+    "pending_deposit_round_volt_tokens",
+    "pending_deposit_round_underlying_tokens",
+    "epoch_info",
+    "system_program",
+    "token_program",
+];
+
+const WITHDRAW_WITH_CLAIM_ACCOUNTS: &[&str] = &[
+    "payer_authority",
+    "non_payer_authority",
+    "authority_check",
+    "vault_mint",
+    "volt_vault",
+    "vault_authority",
+    "extra_volt_data",
+    "deposit_pool",
+    "underlying_token_destination",
+    "vault_token_source",
+    "round_info",
+    "round_underlying_tokens",
+    "pending_withdrawal_info",
+    "pending_withdrawal_round_info",
+    "pending_withdrawal_round_underlying_tokens_for_pws",
+    "epoch_info",
+    "fee_acct",
+    "system_program",
+    "token_program",
+    "rent",
+];
+
+const CLAIM_PENDING_DEPOSIT_ACCOUNTS: &[&str] = &[
+    "authority",
+    "volt_vault",
+    "extra_volt_data",
+    "vault_authority",
+    "user_vault_tokens",
+    "pending_deposit_round_info",
+    "pending_deposit_round_volt_tokens",
+    "pending_deposit_info",
+    "system_program",
+    "token_program",
+];
+
+const CLAIM_PENDING_WITHDRAWAL_ACCOUNTS: &[&str] = &[
+    "authority",
+    "volt_vault",
+    "extra_volt_data",
+    "vault_authority",
+    "vault_mint",
+    "underlying_token_destination",
+    "pending_withdrawal_round_info",
+    "pending_withdrawal_info",
+    "round_underlying_tokens_for_pending_withdrawals",
+    "system_program",
+    "token_program",
+];
+
+const CANCEL_PENDING_DEPOSIT_ACCOUNTS: &[&str] = &[
+    "authority",
+    "volt_vault",
+    "extra_volt_data",
+    "vault_authority",
+    "underlying_token_destination",
+    "round_info",
+    "round_underlying_tokens",
+    "pending_deposit_info",
+    "epoch_info",
+    "system_program",
+    "token_program",
+];
+
+const CANCEL_PENDING_WITHDRAWAL_ACCOUNTS: &[&str] = &[
+    "authority",
+    "vault_mint",
+    "volt_vault",
+    "extra_volt_data",
+    "vault_authority",
+    "vault_token_destination",
+    "round_info",
+    "pending_withdrawal_info",
+    "epoch_info",
+    "system_program",
+    "token_program",
+];
+
+// first 8 bytes of sha256("global:<ix_name>"), as generated by anchor for each handler
+pub fn volt_instruction_sighash(ix_name: &str) -> [u8; 8] {
+    let mut sighash = [0u8; 8];
+    sighash.copy_from_slice(&hash(format!("global:{}", ix_name).as_bytes()).to_bytes()[..8]);
+    sighash
+}
+
+fn decode_args<T: AnchorDeserialize>(mut args: &[u8]) -> Result<T> {
+    T::deserialize(&mut args)
+        .map_err(|_| error!(anchor_lang::error::ErrorCode::InstructionDidNotDeserialize))
+}
+
+impl VoltInstruction {
+    /// decodes instruction data (sighash followed by borsh encoded arguments)
+    pub fn try_from_bytes(data: &[u8]) -> Result<Self> {
+        if data.len() < 8 {
+            return err!(anchor_lang::error::ErrorCode::InstructionMissing);
+        }
+        let (sighash, args) = data.split_at(8);
+
+        Ok(if sighash == volt_instruction_sighash("deposit") {
+            let ix: crate::instruction::Deposit = decode_args(args)?;
+            VoltInstruction::Deposit {
+                deposit_amount: ix.deposit_amount,
+            }
+        } else if sighash == volt_instruction_sighash("withdraw") {
+            let ix: crate::instruction::Withdraw = decode_args(args)?;
+            VoltInstruction::Withdraw { amount: ix.amount }
+        } else if sighash == volt_instruction_sighash("deposit_with_claim") {
+            let ix: crate::instruction::DepositWithClaim = decode_args(args)?;
+            VoltInstruction::DepositWithClaim {
+                amount: ix.amount,
+                do_transfer: ix.do_transfer,
+            }
+        } else if sighash == volt_instruction_sighash("withdraw_with_claim") {
+            let ix: crate::instruction::WithdrawWithClaim = decode_args(args)?;
+            VoltInstruction::WithdrawWithClaim { amount: ix.amount }
+        } else if sighash == volt_instruction_sighash("claim_pending_deposit") {
+            VoltInstruction::ClaimPendingDeposit
+        } else if sighash == volt_instruction_sighash("claim_pending_withdrawal") {
+            VoltInstruction::ClaimPendingWithdrawal
+        } else if sighash == volt_instruction_sighash("cancel_pending_deposit") {
+            VoltInstruction::CancelPendingDeposit
+        } else if sighash == volt_instruction_sighash("cancel_pending_withdrawal") {
+            VoltInstruction::CancelPendingWithdrawal
+        } else {
+            return err!(anchor_lang::error::ErrorCode::InstructionFallbackNotFound);
+        })
+    }
+
+    /// decodes instruction data and labels account_keys (in instruction order) with their field names
+    pub fn decode(data: &[u8], account_keys: &[Pubkey]) -> Result<DecodedVoltInstruction> {
+        let instruction = Self::try_from_bytes(data)?;
+        let names = instruction.account_names();
+        if account_keys.len() < names.len() {
+            return err!(anchor_lang::error::ErrorCode::AccountNotEnoughKeys);
+        }
+        let (keys, remaining_keys) = account_keys.split_at(names.len());
+
+        Ok(DecodedVoltInstruction {
+            accounts: names.iter().copied().zip(keys.iter().copied()).collect(),
+            remaining_accounts: remaining_keys.to_vec(),
+            instruction,
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            VoltInstruction::Deposit { .. } => "deposit",
+            VoltInstruction::Withdraw { .. } => "withdraw",
+            VoltInstruction::DepositWithClaim { .. } => "deposit_with_claim",
+            VoltInstruction::WithdrawWithClaim { .. } => "withdraw_with_claim",
+            VoltInstruction::ClaimPendingDeposit => "claim_pending_deposit",
+            VoltInstruction::ClaimPendingWithdrawal => "claim_pending_withdrawal",
+            VoltInstruction::CancelPendingDeposit => "cancel_pending_deposit",
+            VoltInstruction::CancelPendingWithdrawal => "cancel_pending_withdrawal",
+        }
+    }
+
+    pub fn kind(&self) -> InstructionKind {
+        match self {
+            VoltInstruction::Deposit { .. } => InstructionKind::Deposit,
+            VoltInstruction::Withdraw { .. } => InstructionKind::Withdraw,
+            VoltInstruction::DepositWithClaim { .. } => InstructionKind::DepositWithClaim,
+            VoltInstruction::WithdrawWithClaim { .. } => InstructionKind::WithdrawWithClaim,
+            VoltInstruction::ClaimPendingDeposit => InstructionKind::ClaimPendingDeposit,
+            VoltInstruction::ClaimPendingWithdrawal => InstructionKind::ClaimPendingWithdrawal,
+            VoltInstruction::CancelPendingDeposit => InstructionKind::CancelPendingDeposit,
+            VoltInstruction::CancelPendingWithdrawal => InstructionKind::CancelPendingWithdrawal,
+        }
+    }
+
+    /// field names of the matching Accounts struct, in the order the program expects the accounts
+    pub fn account_names(&self) -> &'static [&'static str] {
+        match self {
+            VoltInstruction::Deposit { .. } => DEPOSIT_ACCOUNTS,
+            VoltInstruction::Withdraw { .. } => WITHDRAW_ACCOUNTS,
+            VoltInstruction::DepositWithClaim { .. } => DEPOSIT_WITH_CLAIM_ACCOUNTS,
+            VoltInstruction::WithdrawWithClaim { .. } => WITHDRAW_WITH_CLAIM_ACCOUNTS,
+            VoltInstruction::ClaimPendingDeposit => CLAIM_PENDING_DEPOSIT_ACCOUNTS,
+            VoltInstruction::ClaimPendingWithdrawal => CLAIM_PENDING_WITHDRAWAL_ACCOUNTS,
+            VoltInstruction::CancelPendingDeposit => CANCEL_PENDING_DEPOSIT_ACCOUNTS,
+            VoltInstruction::CancelPendingWithdrawal => CANCEL_PENDING_WITHDRAWAL_ACCOUNTS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // distinct key per account name, so account metas can be matched back to the field they came from
+    fn key(name: &str) -> Pubkey {
+        Pubkey::new_from_array(hash(name.as_bytes()).to_bytes())
+    }
+
+    // builds crate::accounts::<ix> (so the compiler checks the field list is complete) and checks
+    // its account metas come out in the order of the table
+    macro_rules! assert_account_names {
+        ($table:expr, $ix:ident { $($field:ident),* $(,)? }) => {{
+            let accounts = crate::accounts::$ix {
+                $($field: key(stringify!($field)),)*
+            };
+            let metas = accounts.to_account_metas(None);
+            assert_eq!(metas.len(), $table.len(), "{} account count", stringify!($ix));
+            for (i, (meta, name)) in metas.iter().zip($table.iter()).enumerate() {
+                assert_eq!(meta.pubkey, key(name), "{} account {} should be {}", stringify!($ix), i, name);
+            }
+        }};
+    }
+
+    #[test]
+    fn account_names_match_accounts_structs() {
+        assert_account_names!(
+            DEPOSIT_ACCOUNTS,
+            Deposit {
+                payer_authority,
+                non_payer_authority,
+                authority_check,
+                vault_mint,
+                volt_vault,
+                vault_authority,
+                extra_volt_data,
+                whitelist,
+                deposit_pool,
+                writer_token_pool,
+                user_vault_tokens,
+                user_ul_tokens,
+                round_info,
+                round_volt_tokens,
+                round_underlying_tokens,
+                pending_deposit_info,
+                epoch_info,
+                system_program,
+                token_program
+            }
+        );
+        assert_account_names!(
+            WITHDRAW_ACCOUNTS,
+            Withdraw {
+                payer_authority,
+                non_payer_authority,
+                authority_check,
+                vault_mint,
+                volt_vault,
+                extra_volt_data,
+                vault_authority,
+                whitelist,
+                deposit_pool,
+                underlying_token_destination,
+                vault_token_source,
+                round_info,
+                round_underlying_tokens,
+                pending_withdrawal_info,
+                epoch_info,
+                fee_acct,
+                system_program,
+                token_program,
+                rent
+            }
+        );
+        assert_account_names!(
+            DEPOSIT_WITH_CLAIM_ACCOUNTS,
+            DepositWithClaim {
+                payer_authority,
+                non_payer_authority,
+                sol_transfer_authority,
+                authority_check,
+                vault_mint,
+                volt_vault,
+                vault_authority,
+                extra_volt_data,
+                deposit_pool,
+                writer_token_pool,
+                vault_token_destination,
+                underlying_token_source,
+                round_info,
+                round_underlying_tokens,
+                pending_deposit_info,
+                pending_deposit_round_info,
+                pending_deposit_round_volt_tokens,
+                pending_deposit_round_underlying_tokens,
+                epoch_info,
+                system_program,
+                token_program
+            }
+        );
+        assert_account_names!(
+            WITHDRAW_WITH_CLAIM_ACCOUNTS,
+            WithdrawWithClaim {
+                payer_authority,
+                non_payer_authority,
+                authority_check,
+                vault_mint,
+                volt_vault,
+                vault_authority,
+                extra_volt_data,
+                deposit_pool,
+                underlying_token_destination,
+                vault_token_source,
+                round_info,
+                round_underlying_tokens,
+                pending_withdrawal_info,
+                pending_withdrawal_round_info,
+                pending_withdrawal_round_underlying_tokens_for_pws,
+                epoch_info,
+                fee_acct,
+                system_program,
+                token_program,
+                rent
+            }
+        );
+        assert_account_names!(
+            CLAIM_PENDING_DEPOSIT_ACCOUNTS,
+            ClaimPendingDeposit {
+                authority,
+                volt_vault,
+                extra_volt_data,
+                vault_authority,
+                user_vault_tokens,
+                pending_deposit_round_info,
+                pending_deposit_round_volt_tokens,
+                pending_deposit_info,
+                system_program,
+                token_program
+            }
+        );
+        assert_account_names!(
+            CLAIM_PENDING_WITHDRAWAL_ACCOUNTS,
+            ClaimPendingWithdrawal {
+                authority,
+                volt_vault,
+                extra_volt_data,
+                vault_authority,
+                vault_mint,
+                underlying_token_destination,
+                pending_withdrawal_round_info,
+                pending_withdrawal_info,
+                round_underlying_tokens_for_pending_withdrawals,
+                system_program,
+                token_program
+            }
+        );
+        assert_account_names!(
+            CANCEL_PENDING_DEPOSIT_ACCOUNTS,
+            CancelPendingDeposit {
+                authority,
+                volt_vault,
+                extra_volt_data,
+                vault_authority,
+                underlying_token_destination,
+                round_info,
+                round_underlying_tokens,
+                pending_deposit_info,
+                epoch_info,
+                system_program,
+                token_program
+            }
+        );
+        assert_account_names!(
+            CANCEL_PENDING_WITHDRAWAL_ACCOUNTS,
+            CancelPendingWithdrawal {
+                authority,
+                vault_mint,
+                volt_vault,
+                extra_volt_data,
+                vault_authority,
+                vault_token_destination,
+                round_info,
+                pending_withdrawal_info,
+                epoch_info,
+                system_program,
+                token_program
+            }
+        );
+    }
+
+    #[test]
+    fn decodes_args_and_accounts() {
+        let mut data = volt_instruction_sighash("deposit").to_vec();
+        data.extend_from_slice(&42u64.to_le_bytes());
+        let keys: Vec<Pubkey> = (0..DEPOSIT_ACCOUNTS.len() + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        let decoded = VoltInstruction::decode(&data, &keys).unwrap();
+        assert_eq!(
+            decoded.instruction,
+            VoltInstruction::Deposit { deposit_amount: 42 }
+        );
+        assert_eq!(decoded.accounts[4], ("volt_vault", keys[4]));
+        assert_eq!(decoded.remaining_accounts, keys[DEPOSIT_ACCOUNTS.len()..]);
+        assert_eq!(decoded.instruction.name(), "deposit");
+        assert_eq!(decoded.instruction.kind(), InstructionKind::Deposit);
+
+        assert!(VoltInstruction::decode(&data, &keys[..3]).is_err());
+        assert!(VoltInstruction::try_from_bytes(&data[..10]).is_err());
+        assert!(VoltInstruction::try_from_bytes(&[0; 8]).is_err());
+    }
+
+    #[test]
+    fn sighash_matches_name() {
+        let instructions = [
+            VoltInstruction::ClaimPendingDeposit,
+            VoltInstruction::CancelPendingWithdrawal,
+            VoltInstruction::DepositWithClaim {
+                amount: 1,
+                do_transfer: true,
+            },
+        ];
+        for instruction in instructions {
+            let mut data = volt_instruction_sighash(instruction.name()).to_vec();
+            if let VoltInstruction::DepositWithClaim {
+                amount,
+                do_transfer,
+            } = instruction
+            {
+                data.extend_from_slice(&amount.to_le_bytes());
+                data.push(do_transfer as u8);
+            }
+            assert_eq!(VoltInstruction::try_from_bytes(&data).unwrap(), instruction);
+        }
+    }
+}